@@ -1,7 +1,9 @@
-use std::{collections::HashMap, io::ErrorKind, net::{SocketAddr, UdpSocket}, time::{Duration, Instant}};
+use std::{collections::HashMap, fmt::Display, io::ErrorKind, net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket}, str::FromStr, time::{Duration, Instant}};
 
 use anyhow::{bail, Result};
 use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use zerocopy::IntoBytes;
 
 use crate::packet::{ControllerInfo, Get, Packet, Send, SendControllerData, SendControllerInfo, SendProtocolVersionInfo, BUFFER_SIZE};
 
@@ -11,18 +13,141 @@ pub fn default_address() -> SocketAddr {
     "0.0.0.0:26760".parse().unwrap()
 }
 
-const TIMEOUT: Duration = Duration::from_secs(5);
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Addresses that are allowed to connect. An empty list allows any address.
+    pub allow: Vec<Subnet>,
+    pub max_clients: usize,
+    /// Minimum time between two data packets for the same slot to a single client.
+    pub min_send_interval: Duration,
+    /// Maximum time between two data packets for the same slot, even if it has not changed.
+    pub keepalive_interval: Duration,
+    /// Time after the last data request before a client stops receiving a slot.
+    pub timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            allow: Subnet::LOCALHOST.to_vec(),
+            max_clients: 8,
+            min_send_interval: Duration::from_millis(1),
+            keepalive_interval: Duration::from_millis(200),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn allows(&self, addr: IpAddr) -> bool {
+        self.allow.is_empty() || self.allow.iter().any(|subnet| subnet.contains(addr))
+    }
+}
+
+/// An IP address with a prefix length, e.g. `192.168.1.0/24`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Subnet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Subnet {
+    pub const LOCALHOST: [Self; 2] = [
+        Self { addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 0)), prefix: 8 },
+        Self { addr: IpAddr::V6(Ipv6Addr::LOCALHOST), prefix: 128 },
+    ];
+
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<Self> {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        if prefix > max {
+            bail!("prefix /{prefix} is too long for {addr}");
+        }
+
+        Ok(Self { addr, prefix })
+    }
+
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, addr.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<IpAddr> for Subnet {
+    fn from(addr: IpAddr) -> Self {
+        let prefix = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        Self { addr, prefix }
+    }
+}
+
+impl FromStr for Subnet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('/') {
+            Some((addr, prefix)) => Self::new(addr.parse()?, prefix.parse()?),
+            None => Ok(Self::from(s.parse::<IpAddr>()?)),
+        }
+    }
+}
+
+impl TryFrom<String> for Subnet {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<Subnet> for String {
+    fn from(value: Subnet) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for Subnet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
 
 pub struct Server {
     validate_packets: bool,
     id: u32,
     socket: UdpSocket,
+    config: ServerConfig,
     pub controllers: [SendControllerData; 4],
+    sent: [SendControllerData; 4],
     clients: HashMap<u32, Client>,
+    /// When each refused address was last logged at info level.
+    refused: HashMap<IpAddr, Instant>,
     buf: [u8; BUFFER_SIZE],
 }
 
 impl Server {
+    /// Refused addresses remembered at once, so varied source addresses can't grow the list forever.
+    const MAX_REFUSED: usize = 256;
+    /// Time after which a refused address is logged at info level again.
+    const REFUSED_EXPIRY: Duration = Duration::from_secs(60);
+
     pub fn new(validate_packets: bool, id: u32, addr: SocketAddr) -> Result<Self> {
         let socket = UdpSocket::bind(addr)?;
         socket.set_nonblocking(true)?;
@@ -31,46 +156,94 @@ impl Server {
             validate_packets,
             id,
             socket,
+            config: ServerConfig::default(),
             controllers: std::array::from_fn(|i| SendControllerData::new(ControllerInfo::disconnected(i as u8))),
+            sent: std::array::from_fn(|i| SendControllerData::new(ControllerInfo::disconnected(i as u8))),
             clients: HashMap::new(),
+            refused: HashMap::new(),
             buf: [0; BUFFER_SIZE],
         })
     }
 
+    pub fn with_config(mut self, config: ServerConfig) -> Self {
+        self.set_config(config);
+        self
+    }
+
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Clients that are no longer allowed by the new config are dropped.
+    pub fn set_config(&mut self, config: ServerConfig) {
+        self.config = config;
+        self.refused.clear();
+
+        let config = &self.config;
+        self.clients.retain(|_, client| {
+            let allowed = config.allows(client.addr.ip());
+            if !allowed {
+                info!("client with address {} disconnected: no longer allowed", client.addr);
+            }
+            allowed
+        });
+    }
+
     pub fn remove_old_clients(&mut self) {
+        let now = Instant::now();
+        let timeout = self.config.timeout;
+
         self
             .clients
             .retain(|_, client| {
-                let now = Instant::now();
-                
-                for last_request in &mut client.requesting {
-                    if let Some(instant) = last_request {
-                        if now - *instant > TIMEOUT {
-                            *last_request = None;
-                        }
+                for (slot, last_request) in client.requesting.iter_mut().enumerate() {
+                    if let Some(instant) = last_request
+                        && now - *instant > timeout
+                    {
+                        debug!("client with address {} stopped requesting slot {slot}", client.addr);
+                        *last_request = None;
                     }
                 }
 
-                client.requesting.iter().any(|r| r.is_some())
+                let active = client.requesting.iter().any(|r| r.is_some());
+                if !active {
+                    info!("client with address {} timed out after {timeout:?}", client.addr);
+                }
+                active
             });
     }
 
-    pub fn receive(&mut self) -> Result<()> {
+    /// Handles a single incoming packet. Returns `false` if there was nothing to receive.
+    pub fn receive(&mut self) -> Result<bool> {
         let (len, addr) = match self.socket.recv_from(&mut self.buf) {
             Ok(ok) => ok,
-            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(false),
             Err(e) => return Err(e.into())
         };
 
+        if !self.config.allows(addr.ip()) {
+            self.refuse(addr, "address is not allowed");
+            return Ok(true);
+        }
+
         let bytes = &self.buf[..len];
         let (packet, header) = Packet::parse(bytes, self.validate_packets)?;
 
+        if !self.clients.contains_key(&header.id) && self.clients.len() >= self.config.max_clients {
+            self.refuse(addr, "too many clients");
+            return Ok(true);
+        }
+
+        let refused = &mut self.refused;
         let client = self.clients.entry(header.id)
         .or_insert_with(move || {
             info!("client connected with address {addr}");
+            refused.remove(&addr.ip());
             Client {
                 addr,
                 requesting: [None; 4],
+                last_sent: [None; 4],
+                pending: [true; 4],
                 packet: 0,
             }
         });
@@ -112,22 +285,43 @@ impl Server {
             Packet::Send(_) => bail!("received packet from a server?"),
         }
 
-        Ok(())
+        Ok(true)
     }
 
+    /// Sends every slot that changed since it was last sent, limited by `min_send_interval`,
+    /// and repeats unchanged slots every `keepalive_interval`.
     pub fn send(&mut self) {
         let mut bytes = Vec::new();
+        let now = Instant::now();
 
         for i in 0..4 {
             self.controllers[i].update_connected();
             let mut data = self.controllers[i].clone();
 
+            if data.as_bytes() != self.sent[i].as_bytes() {
+                self.sent[i] = data;
+
+                for client in self.clients.values_mut() {
+                    client.pending[i] = true;
+                }
+            }
+
             for client in self.clients.values_mut() {
                 if client.requesting[i].is_none() { continue; }
 
+                let due = match client.last_sent[i] {
+                    Some(last_sent) if client.pending[i] => now - last_sent >= self.config.min_send_interval,
+                    Some(last_sent) => now - last_sent >= self.config.keepalive_interval,
+                    None => true,
+                };
+
+                if !due { continue; }
+
                 data.packet = client.packet;
                 Packet::Send(Send::SendControllerData(data.clone())).write(self.id, &mut bytes);
                 client.packet += 1;
+                client.last_sent[i] = Some(now);
+                client.pending[i] = false;
 
                 match self.socket.send_to(&bytes, client.addr) {
                     Ok(_) => {}
@@ -138,10 +332,49 @@ impl Server {
             }
         }
     }
+
+    /// Time until `send` next has something to do if no slot changes in the meantime.
+    pub fn next_send(&self) -> Duration {
+        let now = Instant::now();
+        let mut next = self.config.keepalive_interval;
+
+        for client in self.clients.values() {
+            for i in 0..4 {
+                if client.requesting[i].is_none() { continue; }
+
+                let Some(last_sent) = client.last_sent[i]
+                else { return Duration::ZERO };
+
+                let interval = match client.pending[i] {
+                    true => self.config.min_send_interval,
+                    false => self.config.keepalive_interval,
+                };
+
+                next = next.min((last_sent + interval).saturating_duration_since(now));
+            }
+        }
+
+        next
+    }
+
+    /// Only the first refusal for each address is logged at info level, since clients retry constantly.
+    fn refuse(&mut self, addr: SocketAddr, reason: &str) {
+        let now = Instant::now();
+        self.refused.retain(|_, &mut refused| now - refused < Self::REFUSED_EXPIRY);
+
+        if !self.refused.contains_key(&addr.ip()) && self.refused.len() < Self::MAX_REFUSED {
+            self.refused.insert(addr.ip(), now);
+            info!("refused client with address {addr}: {reason}");
+        } else {
+            debug!("refused client with address {addr}: {reason}");
+        }
+    }
 }
 
 struct Client {
     addr: SocketAddr,
     requesting: [Option<Instant>; 4],
+    last_sent: [Option<Instant>; 4],
+    pending: [bool; 4],
     packet: u32,
-}
\ No newline at end of file
+}
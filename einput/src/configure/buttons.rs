@@ -1,5 +1,19 @@
-use eframe::egui::{ComboBox, Frame, ScrollArea, Stroke, Ui};
-use einput_device::input::buttons::Button;
+use std::hash::Hash;
+
+use eframe::egui::{Color32, ComboBox, DragValue, Frame, ScrollArea, Slider, Stroke, Ui};
+use einput_config::{
+    debounce::{ChatterStats, Debounce},
+    input::{ButtonAction, ButtonTargets},
+    macros::Macro,
+    mapping::Direction,
+    socd::SocdMode,
+    turbo::{Turbo, TurboMode},
+};
+use einput_device::input::{
+    buttons::{Button, Buttons},
+    sticks::StickId,
+    triggers::TriggerId,
+};
 
 use super::Configure;
 
@@ -8,6 +22,22 @@ impl Configure {
         ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
+                if ui.button("Reset Chatter Stats").clicked() {
+                    self.config.input.chatter.reset();
+                }
+
+                let socd = &mut self.config.input.socd;
+                let mut changed = false;
+
+                ui.horizontal(|ui| {
+                    changed |= socd_select(ui, "Left + Right", &mut socd.horizontal);
+                    changed |= socd_select(ui, "Up + Down", &mut socd.vertical);
+                });
+
+                if changed {
+                    self.update_config();
+                }
+
                 for button in Button::ALL {
                     let stroke = if self
                         .get_raw_input()
@@ -23,25 +53,28 @@ impl Configure {
                         .inner_margin(2.0)
                         .stroke(stroke)
                         .show(ui, |ui| {
-                            let mut selected = None;
-
-                            ComboBox::from_label(format!("{button:?}"))
-                                .selected_text(format!(
-                                    "{:?}",
-                                    self.config.input.buttons[button as usize]
-                                ))
-                                .show_ui(ui, |ui| {
-                                    for button in Button::ALL {
-                                        ui.selectable_value(
-                                            &mut selected,
-                                            Some(button),
-                                            format!("{button:?}"),
-                                        );
-                                    }
-                                });
-
-                            if let Some(new) = selected {
-                                self.config.input.buttons[button as usize] = new;
+                            let targets = &mut self.config.input.buttons[button as usize];
+                            let mut changed = false;
+
+                            ui.horizontal(|ui| {
+                                changed |= targets_select(ui, button, targets, &self.config.macros);
+                                ui.label(format!("{button:?}"));
+                            });
+
+                            ui.horizontal(|ui| {
+                                changed |= ui.checkbox(&mut self.config.input.latch[button as usize], "Latch").changed();
+                                changed |= turbo_ui(ui, &mut self.config.input.turbo[button as usize]);
+                            });
+
+                            ui.horizontal(|ui| {
+                                changed |= debounce_ui(ui, &mut self.config.input.debounce[button as usize]);
+
+                                if self.config.input.debounce[button as usize].is_some_and(|d| d.count_chatter) {
+                                    chatter_ui(ui, &self.config.input.chatter, button);
+                                }
+                            });
+
+                            if changed {
                                 self.update_config();
                             }
                         });
@@ -49,3 +82,156 @@ impl Configure {
             });
     }
 }
+
+/// Shows a combo box for choosing any number of buttons.
+///
+/// Returns true if buttons were changed
+pub fn buttons_select(ui: &mut Ui, id_source: impl Hash, buttons: &mut Buttons) -> bool {
+    let mut changed = false;
+
+    ComboBox::from_id_source(id_source)
+        .selected_text(ButtonTargets::from(*buttons).name())
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(*buttons == Buttons::default(), "None").clicked() {
+                *buttons = Buttons::default();
+                changed = true;
+            }
+
+            for button in Button::ALL {
+                let pressed = buttons.is_pressed(button);
+
+                if ui.selectable_label(pressed, format!("{button:?}")).clicked() {
+                    buttons.set(button, !pressed);
+                    changed = true;
+                }
+            }
+        });
+
+    changed
+}
+
+/// Shows a combo box for choosing any number of buttons, triggers, stick directions and macros.
+///
+/// Returns true if targets were changed
+pub fn targets_select(ui: &mut Ui, id_source: impl Hash, targets: &mut ButtonTargets, macros: &[Macro]) -> bool {
+    let mut changed = false;
+
+    ComboBox::from_id_source(id_source)
+        .selected_text(targets.name())
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(*targets == ButtonTargets::default(), "None").clicked() {
+                *targets = ButtonTargets::default();
+                changed = true;
+            }
+
+            for button in Button::ALL {
+                let pressed = targets.buttons.is_pressed(button);
+
+                if ui.selectable_label(pressed, format!("{button:?}")).clicked() {
+                    targets.buttons.set(button, !pressed);
+                    changed = true;
+                }
+            }
+
+            ui.separator();
+
+            let triggers = TriggerId::ALL.map(ButtonAction::Trigger);
+            let sticks = StickId::ALL
+                .into_iter()
+                .flat_map(|id| Direction::ALL.map(|direction| ButtonAction::Stick { id, direction }));
+
+            for action in triggers.into_iter().chain(sticks) {
+                changed |= action_label(ui, targets, action, action.name());
+            }
+
+            if !macros.is_empty() {
+                ui.separator();
+            }
+
+            for (i, m) in macros.iter().enumerate() {
+                changed |= action_label(ui, targets, ButtonAction::Macro(i), format!("Macro: {}", m.name));
+            }
+        });
+
+    changed
+}
+
+/// Returns true if the action was toggled
+fn action_label(ui: &mut Ui, targets: &mut ButtonTargets, action: ButtonAction, text: String) -> bool {
+    let pressed = targets.has_action(action);
+
+    if ui.selectable_label(pressed, text).clicked() {
+        targets.set_action(action, !pressed);
+        return true;
+    }
+
+    false
+}
+
+/// Returns true if turbo was changed
+fn turbo_ui(ui: &mut Ui, turbo: &mut Option<Turbo>) -> bool {
+    let mut enabled = turbo.is_some();
+    let mut changed = false;
+
+    if ui.checkbox(&mut enabled, "Turbo").changed() {
+        *turbo = enabled.then(Turbo::default);
+        changed = true;
+    }
+
+    if let Some(turbo) = turbo {
+        changed |= ui.add(Slider::new(&mut turbo.rate, 1.0..=30.0).text("Rate").suffix("/s")).changed();
+        changed |= ui.add(Slider::new(&mut turbo.duty, 0.05..=0.95).text("Duty")).changed();
+
+        for mode in TurboMode::ALL {
+            changed |= ui.radio_value(&mut turbo.mode, mode, mode.name()).changed();
+        }
+    }
+
+    changed
+}
+
+/// Returns true if the mode was changed
+fn socd_select(ui: &mut Ui, label: &str, mode: &mut SocdMode) -> bool {
+    let mut changed = false;
+
+    ComboBox::from_label(label)
+        .selected_text(mode.name())
+        .show_ui(ui, |ui| {
+            for m in SocdMode::ALL {
+                changed |= ui.selectable_value(mode, m, m.name()).changed();
+            }
+        });
+
+    changed
+}
+
+/// Returns true if debounce was changed
+fn debounce_ui(ui: &mut Ui, debounce: &mut Option<Debounce>) -> bool {
+    let mut enabled = debounce.is_some();
+    let mut changed = false;
+
+    if ui.checkbox(&mut enabled, "Debounce").changed() {
+        *debounce = enabled.then(Debounce::default);
+        changed = true;
+    }
+
+    if let Some(debounce) = debounce {
+        ui.label("Press");
+        changed |= ui.add(DragValue::new(&mut debounce.press).clamp_range(0..=200).suffix(" ms")).changed();
+        ui.label("Release");
+        changed |= ui.add(DragValue::new(&mut debounce.release).clamp_range(0..=200).suffix(" ms")).changed();
+        changed |= ui.checkbox(&mut debounce.count_chatter, "Count Chatter").changed();
+    }
+
+    changed
+}
+
+fn chatter_ui(ui: &mut Ui, stats: &ChatterStats, button: Button) {
+    let (presses, chatter) = stats.get(button);
+
+    ui.label(format!("Presses: {presses}, Chatter: {chatter}"));
+
+    if stats.failing(button) {
+        ui.colored_label(Color32::RED, "Switch may be failing");
+    }
+}
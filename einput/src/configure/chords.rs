@@ -0,0 +1,90 @@
+use eframe::egui::{Frame, ScrollArea, Slider, Stroke, Ui};
+use einput_config::chord::Chord;
+use einput_device::input::buttons::Buttons;
+
+use super::{
+    buttons::{buttons_select, targets_select},
+    Configure,
+};
+
+impl Configure {
+    pub fn tab_chords(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Add Chord").clicked() {
+                self.config.input.chords.push(Chord::default());
+                self.update_config();
+            }
+
+            ui.weak("Suppressed buttons are held back for the chord's window, which delays their own presses.");
+        });
+
+        ui.separator();
+
+        let held = self
+            .raw_reader
+            .current()
+            .values()
+            .next()
+            .and_then(|input| input.buttons())
+            .copied()
+            .unwrap_or_default();
+
+        let mut changed = false;
+        let mut remove = None;
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (i, chord) in self.config.input.chords.iter_mut().enumerate() {
+                    let components = chord.components();
+
+                    let stroke = if components != Buttons::default() && held.0 & components.0 == components.0 {
+                        ui.visuals().widgets.active.fg_stroke
+                    } else {
+                        Stroke::NONE
+                    };
+
+                    Frame::none()
+                        .inner_margin(2.0)
+                        .stroke(stroke)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                let mut components = chord.components();
+
+                                if buttons_select(ui, ("chord", i), &mut components) {
+                                    chord.buttons = components.get_pressed().collect();
+                                    changed = true;
+                                }
+
+                                ui.label("→");
+
+                                changed |= targets_select(ui, ("chord_output", i), &mut chord.output, &self.config.macros);
+
+                                if ui.button("Remove").clicked() {
+                                    remove = Some(i);
+                                }
+                            });
+
+                            ui.horizontal(|ui| {
+                                changed |= ui
+                                    .add(Slider::new(&mut chord.window, 0..=1000).text("Window").suffix(" ms"))
+                                    .changed();
+                                changed |= ui
+                                    .checkbox(&mut chord.suppress, "Suppress Buttons")
+                                    .on_hover_text("Delays presses of the chord's buttons by up to the window")
+                                    .changed();
+                            });
+                        });
+                }
+            });
+
+        if let Some(i) = remove {
+            self.config.input.chords.remove(i);
+            changed = true;
+        }
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
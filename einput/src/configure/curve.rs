@@ -0,0 +1,125 @@
+use eframe::egui::{self, Color32, ComboBox, DragValue, Rounding, Sense, Slider, Stroke, Ui, Vec2};
+use einput_config::curve::Curve;
+
+/// Returns true if curve was changed
+pub fn curve_ui(ui: &mut Ui, name: &str, curve: &mut Curve, raw: f32, configured: f32) -> bool {
+    let mut changed = false;
+    // Sorting moves points between rows, so it waits until a point's input is no longer being edited
+    let mut editing = false;
+    let mut edited = false;
+
+    ui.label(name);
+
+    ui.horizontal(|ui| {
+        draw_curve(ui, curve, raw, configured);
+
+        ui.vertical(|ui| {
+            let mut selected = None;
+
+            ComboBox::from_id_source(name)
+                .selected_text(curve.name())
+                .show_ui(ui, |ui| {
+                    for new in [
+                        Curve::Linear,
+                        Curve::Power(2.0),
+                        Curve::Exponential(3.0),
+                        Curve::Points(vec![[0.5, 0.25]]),
+                    ] {
+                        let text = new.name();
+                        ui.selectable_value(&mut selected, Some(new), text);
+                    }
+                });
+
+            if let Some(new) = selected {
+                if std::mem::discriminant(&new) != std::mem::discriminant(curve) {
+                    *curve = new;
+                    changed = true;
+                }
+            }
+
+            match curve {
+                Curve::Linear => {}
+                Curve::Power(exponent) => {
+                    changed |= ui.add(Slider::new(exponent, 0.1..=5.0).text("Exponent")).changed();
+                }
+                Curve::Exponential(steepness) => {
+                    changed |= ui.add(Slider::new(steepness, -10.0..=10.0).text("Steepness")).changed();
+                }
+                Curve::Points(points) => {
+                    let mut remove = None;
+
+                    for (i, [x, y]) in points.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            let response = ui.add(DragValue::new(x).speed(0.01).clamp_range(0.0..=1.0).prefix("In: "));
+                            changed |= response.changed();
+                            editing |= response.dragged() || response.has_focus();
+                            edited |= response.drag_stopped() || response.lost_focus();
+
+                            changed |= ui.add(DragValue::new(y).speed(0.01).clamp_range(0.0..=1.0).prefix("Out: ")).changed();
+
+                            if ui.small_button("x").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+
+                    if let Some(i) = remove {
+                        points.remove(i);
+                        changed = true;
+                    }
+
+                    if ui.button("Add Point").clicked() {
+                        let [x, y] = points.last().copied().unwrap_or([0.0, 0.0]);
+                        points.push([(x + 1.0) / 2.0, (y + 1.0) / 2.0]);
+                        changed = true;
+                    }
+                }
+            }
+        });
+    });
+
+    changed |= edited;
+
+    if changed && !editing {
+        curve.sort();
+    }
+
+    changed
+}
+
+fn draw_curve(ui: &mut Ui, curve: &Curve, raw: f32, configured: f32) {
+    let size = 100.0;
+
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(size), Sense::hover());
+
+    let to_screen = |x: f32, y: f32| rect.left_bottom() + Vec2::new(x, -y) * size;
+
+    // Rect
+    ui.painter().rect(
+        rect,
+        Rounding::ZERO,
+        Color32::TRANSPARENT,
+        ui.visuals().widgets.inactive.fg_stroke,
+    );
+
+    // Curve
+    let points = (0..=50)
+        .map(|i| {
+            let x = i as f32 / 50.0;
+            to_screen(x, curve.apply(x))
+        })
+        .collect();
+
+    ui.painter().add(egui::Shape::line(
+        points,
+        Stroke::new(2.0, ui.visuals().hyperlink_color),
+    ));
+
+    // Input
+    ui.painter().circle(
+        to_screen(raw.min(1.0), configured.min(1.0)),
+        2.0,
+        ui.visuals().strong_text_color(),
+        Stroke::NONE,
+    );
+}
@@ -0,0 +1,91 @@
+use eframe::egui::{DragValue, Slider, Ui};
+use einput_config::flick::FlickStick;
+use einput_device::input::sticks::StickId;
+
+use crate::widgets::stick_input::StickInput;
+
+use super::Configure;
+
+pub struct FlickTab {
+    /// Degrees the game actually turned during a test turn.
+    measured: f32,
+}
+
+impl Default for FlickTab {
+    fn default() -> Self {
+        Self { measured: 360.0 }
+    }
+}
+
+impl Configure {
+    pub fn tab_flick(&mut self, ui: &mut Ui) {
+        let mut enabled = self.config.flick_stick.is_some();
+
+        if ui.checkbox(&mut enabled, "Flick Stick").changed() {
+            self.config.flick_stick = enabled.then(FlickStick::default);
+            self.update_config();
+        }
+
+        let Some(flick) = &mut self.config.flick_stick
+        else { return };
+
+        let stick = self
+            .reader
+            .current()
+            .values()
+            .next()
+            .and_then(|input| input.get(flick.output))
+            .copied();
+
+        if let Some(stick) = stick {
+            ui.add(StickInput::new(stick, format!("{:?}", flick.output)));
+        }
+
+        ui.separator();
+
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Input");
+
+            for id in StickId::ALL {
+                changed |= ui.radio_value(&mut flick.input, id, id.name()).changed();
+            }
+
+            ui.separator();
+            ui.label("Output");
+
+            for id in StickId::ALL {
+                changed |= ui.radio_value(&mut flick.output, id, id.name()).changed();
+            }
+        });
+
+        changed |= ui.add(Slider::new(&mut flick.threshold, 0.1..=1.0).text("Threshold")).changed();
+        changed |= ui.add(Slider::new(&mut flick.flick_time, 0.0..=0.5).text("Flick Time").suffix(" s")).changed();
+        changed |= ui
+            .add(Slider::new(&mut flick.turn_rate, 30.0..=2000.0).logarithmic(true).text("Turn Rate").suffix("°/s"))
+            .changed();
+
+        ui.separator();
+
+        ui.label("Turn a full circle in game, then enter how far it actually turned to correct the turn rate");
+
+        ui.horizontal(|ui| {
+            if ui.button("Turn 360°").clicked() {
+                flick.test.start();
+            }
+
+            ui.add(DragValue::new(&mut self.tab_flick.measured).clamp_range(1.0..=3600.0).suffix("°"));
+
+            if ui.button("Correct").clicked() {
+                flick.turn_rate *= self.tab_flick.measured / 360.0;
+                self.tab_flick.measured = 360.0;
+                changed = true;
+            }
+        });
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use eframe::egui::{ComboBox, RichText, Slider, Ui};
+use einput_config::gyro::{GyroButtonMode, GyroMode, GyroStick};
+use einput_device::input::{buttons::Button, sticks::StickId};
+
+use crate::widgets::stick_input::StickInput;
+
+use super::Configure;
+
+impl Configure {
+    pub fn tab_gyro(&mut self, ui: &mut Ui) {
+        let Some(gyroscope) = self.get_raw_input().and_then(|input| input.gyroscope()).copied()
+        else {
+            ui.label("This device has no gyroscope");
+            return;
+        };
+
+        ui.label(
+            RichText::new(format!(
+                "Pitch: {:>8.02}, Roll: {:>8.02}, Yaw: {:>8.02}",
+                gyroscope.pitch, gyroscope.roll, gyroscope.yaw
+            ))
+            .monospace(),
+        );
+
+        let mut enabled = self.config.gyro.is_some();
+
+        if ui.checkbox(&mut enabled, "Gyro to Stick").changed() {
+            self.config.gyro = enabled.then(GyroStick::default);
+            self.update_config();
+        }
+
+        let Some(gyro) = &mut self.config.gyro
+        else { return };
+
+        let stick = self
+            .reader
+            .current()
+            .values()
+            .next()
+            .and_then(|input| input.get(gyro.stick))
+            .copied();
+
+        if let Some(stick) = stick {
+            ui.add(StickInput::new(stick, format!("{:?}", gyro.stick)));
+        }
+
+        ui.separator();
+
+        if gyro_ui(ui, gyro) {
+            self.update_config();
+        }
+    }
+}
+
+/// Returns true if the gyro was changed
+fn gyro_ui(ui: &mut Ui, gyro: &mut GyroStick) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        for id in StickId::ALL {
+            changed |= ui.radio_value(&mut gyro.stick, id, id.name()).changed();
+        }
+
+        ui.separator();
+
+        for mode in GyroMode::ALL {
+            changed |= ui.radio_value(&mut gyro.mode, mode, mode.name()).changed();
+        }
+    });
+
+    changed |= ui.add(Slider::new(&mut gyro.sensitivity, 0.1..=10.0).logarithmic(true).text("Sensitivity")).changed();
+    changed |= ui.add(Slider::new(&mut gyro.deadzone, 0.0..=20.0).text("Deadzone").suffix("°/s")).changed();
+    changed |= ui.add(Slider::new(&mut gyro.smoothing, 0.0..=0.5).text("Smoothing").suffix(" s")).changed();
+
+    ui.horizontal(|ui| {
+        changed |= ui.checkbox(&mut gyro.invert[0], "Invert X").changed();
+        changed |= ui.checkbox(&mut gyro.invert[1], "Invert Y").changed();
+    });
+
+    ui.horizontal(|ui| {
+        ComboBox::from_id_source("gyro_button")
+            .selected_text(gyro.button.map_or("None".to_owned(), |button| format!("{button:?}")))
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(&mut gyro.button, None, "None").changed();
+
+                for button in Button::ALL {
+                    changed |= ui.selectable_value(&mut gyro.button, Some(button), format!("{button:?}")).changed();
+                }
+            });
+
+        ui.label("Button");
+
+        for mode in GyroButtonMode::ALL {
+            changed |= ui.radio_value(&mut gyro.button_mode, mode, mode.name()).changed();
+        }
+    });
+
+    changed
+}
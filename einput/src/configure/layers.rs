@@ -0,0 +1,91 @@
+use eframe::egui::{CollapsingHeader, ComboBox, ScrollArea, Ui};
+use einput_config::{
+    input::ButtonTargets,
+    layer::{Layer, LayerMode},
+};
+use einput_device::input::buttons::Button;
+
+use super::{buttons::targets_select, mappings::mappings_ui, Configure};
+
+impl Configure {
+    pub fn tab_layers(&mut self, ui: &mut Ui) {
+        if ui.button("Add Layer").clicked() {
+            self.config.layers.push(Layer::default());
+            self.update_config();
+        }
+
+        ui.separator();
+
+        let raw = self.raw_reader.current().values().next();
+
+        let mut changed = false;
+        let mut remove = None;
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (i, layer) in self.config.layers.iter_mut().enumerate() {
+                    ui.push_id(i, |ui| {
+                        ui.horizontal(|ui| {
+                            changed |= ui.text_edit_singleline(&mut layer.name).changed();
+
+                            if ui.button("Remove").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ComboBox::from_label("Button")
+                                .selected_text(format!("{:?}", layer.button))
+                                .show_ui(ui, |ui| {
+                                    for button in Button::ALL {
+                                        changed |= ui
+                                            .selectable_value(&mut layer.button, button, format!("{button:?}"))
+                                            .changed();
+                                    }
+                                });
+
+                            for mode in LayerMode::ALL {
+                                changed |= ui.radio_value(&mut layer.mode, mode, mode.name()).changed();
+                            }
+                        });
+
+                        CollapsingHeader::new("Buttons").show(ui, |ui| {
+                            for button in Button::ALL {
+                                ui.horizontal(|ui| {
+                                    let targets = &mut layer.buttons[button as usize];
+                                    let mut overridden = targets.is_some();
+
+                                    if ui.checkbox(&mut overridden, format!("{button:?}")).changed() {
+                                        *targets = overridden.then(|| ButtonTargets::from(button));
+                                        changed = true;
+                                    }
+
+                                    if let Some(targets) = targets {
+                                        changed |= targets_select(ui, button, targets, &self.config.macros);
+                                    } else {
+                                        ui.label("Base Layer");
+                                    }
+                                });
+                            }
+                        });
+
+                        CollapsingHeader::new("Mappings").show(ui, |ui| {
+                            changed |= mappings_ui(ui, raw, &mut layer.mappings);
+                        });
+                    });
+
+                    ui.separator();
+                }
+            });
+
+        if let Some(i) = remove {
+            self.config.layers.remove(i);
+            changed = true;
+        }
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use eframe::egui::{CollapsingHeader, DragValue, ScrollArea, Slider, Ui};
+use einput_config::macros::{Macro, MacroStep};
+use einput_device::input::{sticks::StickId, triggers::TriggerId};
+
+use super::{buttons::buttons_select, Configure};
+
+impl Configure {
+    pub fn tab_macros(&mut self, ui: &mut Ui) {
+        if ui.button("Add Macro").clicked() {
+            self.config.macros.push(Macro::default());
+            self.update_config();
+        }
+
+        ui.separator();
+
+        let mut changed = false;
+        let mut remove = None;
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (i, m) in self.config.macros.iter_mut().enumerate() {
+                    ui.push_id(i, |ui| {
+                        ui.horizontal(|ui| {
+                            changed |= ui.text_edit_singleline(&mut m.name).changed();
+
+                            if ui.button("Remove").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            let mut components = m.components();
+
+                            if buttons_select(ui, "buttons", &mut components) {
+                                m.buttons = components.get_pressed().collect();
+                                changed = true;
+                            }
+
+                            ui.label("Buttons");
+
+                            changed |= ui.checkbox(&mut m.suppress, "Suppress Buttons").changed();
+
+                            if m.suppress {
+                                changed |= ui
+                                    .add(Slider::new(&mut m.window, 0..=1000).text("Window").suffix(" ms"))
+                                    .changed();
+                            }
+                        });
+
+                        CollapsingHeader::new(format!("Steps ({})", m.steps.len()))
+                            .id_source("steps")
+                            .show(ui, |ui| {
+                                changed |= steps_ui(ui, &mut m.steps);
+                            });
+                    });
+
+                    ui.separator();
+                }
+            });
+
+        if let Some(i) = remove {
+            self.config.remove_macro(i);
+            changed = true;
+        }
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
+
+/// Returns true if steps were changed
+fn steps_ui(ui: &mut Ui, steps: &mut Vec<MacroStep>) -> bool {
+    let mut changed = false;
+    let mut remove = None;
+
+    for (i, step) in steps.iter_mut().enumerate() {
+        ui.push_id(i, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}.", i + 1));
+
+                changed |= ui
+                    .add(DragValue::new(&mut step.duration).clamp_range(1..=10000).suffix(" ms"))
+                    .changed();
+
+                changed |= buttons_select(ui, "buttons", &mut step.buttons.buttons);
+
+                if ui.button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                for id in StickId::ALL {
+                    let stick = &mut step.sticks[id as usize];
+                    let mut enabled = stick.is_some();
+
+                    if ui.checkbox(&mut enabled, format!("{id:?} Stick")).changed() {
+                        *stick = enabled.then_some([0.0; 2]);
+                        changed = true;
+                    }
+
+                    if let Some([x, y]) = stick {
+                        changed |= ui.add(DragValue::new(x).speed(0.01).clamp_range(-1.0..=1.0).prefix("X: ")).changed();
+                        changed |= ui.add(DragValue::new(y).speed(0.01).clamp_range(-1.0..=1.0).prefix("Y: ")).changed();
+                    }
+                }
+            });
+
+            ui.horizontal_wrapped(|ui| {
+                for id in TriggerId::ALL {
+                    let trigger = &mut step.triggers[id as usize];
+                    let mut enabled = trigger.is_some();
+
+                    if ui.checkbox(&mut enabled, format!("{id:?}")).changed() {
+                        *trigger = enabled.then_some(1.0);
+                        changed = true;
+                    }
+
+                    if let Some(value) = trigger {
+                        changed |= ui.add(Slider::new(value, 0.0..=1.0)).changed();
+                    }
+                }
+            });
+        });
+    }
+
+    if let Some(i) = remove {
+        steps.remove(i);
+        changed = true;
+    }
+
+    if ui.button("Add Step").clicked() {
+        steps.push(MacroStep::default());
+        changed = true;
+    }
+
+    changed
+}
@@ -0,0 +1,206 @@
+use eframe::egui::{ComboBox, Frame, ScrollArea, Slider, Stroke, Ui};
+use einput_config::mapping::{Actuation, Direction, Mapping, Sectors, Source, Target};
+use einput_device::input::{buttons::Button, sticks::StickId, triggers::TriggerId, DeviceInput};
+
+use super::Configure;
+
+impl Configure {
+    pub fn tab_mappings(&mut self, ui: &mut Ui) {
+        let raw = self.raw_reader.current().values().next();
+        let mut changed = false;
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                changed = mappings_ui(ui, raw, &mut self.config.input.mappings);
+            });
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
+
+/// Returns true if mappings were changed
+pub fn mappings_ui(ui: &mut Ui, raw: Option<&DeviceInput>, mappings: &mut Vec<Mapping>) -> bool {
+    let mut changed = false;
+
+    if ui.button("Add Mapping").clicked() {
+        mappings.push(Mapping::new(
+            Source::Button(Button::A),
+            Target::Button(Button::A),
+        ));
+        changed = true;
+    }
+
+    ui.separator();
+
+    let mut remove = None;
+
+    for (i, mapping) in mappings.iter_mut().enumerate() {
+        let active = raw
+            .map(|input| mapping.from.value(input) > 0.0)
+            .unwrap_or(false);
+
+        let stroke = if active {
+            ui.visuals().widgets.active.fg_stroke
+        } else {
+            Stroke::NONE
+        };
+
+        Frame::none()
+            .inner_margin(2.0)
+            .stroke(stroke)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.push_id(i, |ui| {
+                        changed |= source_ui(ui, &mut mapping.from);
+                        ui.label("→");
+                        changed |= target_ui(ui, &mut mapping.to);
+
+                        if ui.button("Remove").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                });
+
+                if mapping.from.is_analog() && matches!(mapping.to, Target::Button(_)) {
+                    ui.horizontal(|ui| {
+                        changed |= actuation_ui(ui, &mut mapping.actuation);
+                    });
+                }
+            });
+    }
+
+    if let Some(i) = remove {
+        mappings.remove(i);
+        changed = true;
+    }
+
+    changed
+}
+
+/// Returns true if source was changed
+fn source_ui(ui: &mut Ui, source: &mut Source) -> bool {
+    let mut selected = None;
+
+    ComboBox::from_id_source("source")
+        .selected_text(source.name())
+        .show_ui(ui, |ui| {
+            for button in Button::ALL {
+                let new = Source::Button(button);
+                ui.selectable_value(&mut selected, Some(new), new.name());
+            }
+
+            for id in TriggerId::ALL {
+                let new = Source::Trigger { id, threshold: 0.5 };
+                ui.selectable_value(&mut selected, Some(new), new.name());
+            }
+
+            for id in StickId::ALL {
+                for direction in Direction::ALL {
+                    let new = Source::Stick {
+                        id,
+                        direction,
+                        sectors: Sectors::Four,
+                        threshold: 0.5,
+                    };
+                    ui.selectable_value(&mut selected, Some(new), new.name());
+                }
+            }
+        });
+
+    let mut changed = false;
+
+    if let Some(new) = selected {
+        *source = new;
+        changed = true;
+    }
+
+    match source {
+        Source::Button(_) => {}
+        Source::Trigger { threshold, .. } => {
+            changed |= ui.add(Slider::new(threshold, 0.0..=1.0).text("Threshold")).changed();
+        }
+        Source::Stick {
+            sectors, threshold, ..
+        } => {
+            changed |= ui.add(Slider::new(threshold, 0.0..=1.0).text("Threshold")).changed();
+
+            for new in Sectors::ALL {
+                let text = match new {
+                    Sectors::Four => "4-Way",
+                    Sectors::Eight => "8-Way",
+                };
+                changed |= ui.radio_value(sectors, new, text).changed();
+            }
+        }
+    }
+
+    changed
+}
+
+/// Returns true if actuation was changed
+fn actuation_ui(ui: &mut Ui, actuation: &mut Actuation) -> bool {
+    let mut changed = false;
+
+    for new in [
+        Actuation::Threshold,
+        Actuation::Hysteresis { release: 0.1 },
+        Actuation::Rapid { press: 0.1, release: 0.1 },
+    ] {
+        let selected = std::mem::discriminant(actuation) == std::mem::discriminant(&new);
+
+        if ui.radio(selected, new.name()).clicked() && !selected {
+            *actuation = new;
+            changed = true;
+        }
+    }
+
+    match actuation {
+        Actuation::Threshold => {}
+        Actuation::Hysteresis { release } => {
+            changed |= ui.add(Slider::new(release, 0.0..=1.0).text("Release Distance")).changed();
+        }
+        Actuation::Rapid { press, release } => {
+            changed |= ui.add(Slider::new(press, 0.01..=1.0).text("Press Distance")).changed();
+            changed |= ui.add(Slider::new(release, 0.01..=1.0).text("Release Distance")).changed();
+        }
+    }
+
+    changed
+}
+
+/// Returns true if target was changed
+fn target_ui(ui: &mut Ui, target: &mut Target) -> bool {
+    let mut selected = None;
+
+    ComboBox::from_id_source("target")
+        .selected_text(target.name())
+        .show_ui(ui, |ui| {
+            for button in Button::ALL {
+                let new = Target::Button(button);
+                ui.selectable_value(&mut selected, Some(new), new.name());
+            }
+
+            for id in TriggerId::ALL {
+                let new = Target::Trigger(id);
+                ui.selectable_value(&mut selected, Some(new), new.name());
+            }
+
+            for id in StickId::ALL {
+                for direction in Direction::ALL {
+                    let new = Target::Stick { id, direction };
+                    ui.selectable_value(&mut selected, Some(new), new.name());
+                }
+            }
+        });
+
+    match selected {
+        Some(new) => {
+            *target = new;
+            true
+        }
+        None => false,
+    }
+}
@@ -10,13 +10,22 @@ use einput_device::DeviceInput;
 
 use crate::Configs;
 
-use self::{load::LoadTab, save::SaveTab, sticks::SticksTab};
+use self::{flick::FlickTab, load::LoadTab, save::SaveTab, sticks::SticksTab, triggers::TriggersTab};
 
 mod buttons;
+mod chords;
+mod curve;
+mod flick;
+mod gyro;
+mod layers;
 mod load;
+mod macros;
+mod mappings;
 mod overview;
+mod rest;
 mod save;
 mod sticks;
+mod tilt;
 mod triggers;
 
 pub struct Configure {
@@ -29,9 +38,11 @@ pub struct Configure {
     configs: Arc<Mutex<Configs>>,
 
     tab: Tab,
+    tab_flick: FlickTab,
     tab_load: LoadTab,
     tab_save: SaveTab,
     tab_sticks: SticksTab,
+    tab_triggers: TriggersTab,
 }
 
 impl Configure {
@@ -54,9 +65,11 @@ impl Configure {
             configs,
 
             tab: Tab::Overview,
+            tab_flick: FlickTab::default(),
             tab_load: LoadTab::default(),
             tab_save: SaveTab::default(),
             tab_sticks: SticksTab::default(),
+            tab_triggers: TriggersTab::default(),
         }
     }
 
@@ -83,6 +96,13 @@ impl Configure {
                         self.tab_select(ui, Tab::Buttons);
                         self.tab_select(ui, Tab::Sticks);
                         self.tab_select(ui, Tab::Triggers);
+                        self.tab_select(ui, Tab::Mappings);
+                        self.tab_select(ui, Tab::Chords);
+                        self.tab_select(ui, Tab::Layers);
+                        self.tab_select(ui, Tab::Macros);
+                        self.tab_select(ui, Tab::Flick);
+                        self.tab_select(ui, Tab::Gyro);
+                        self.tab_select(ui, Tab::Tilt);
 
                         ui.add_space(5.0);
                         ui.label(RichText::new("Config").strong());
@@ -100,6 +120,13 @@ impl Configure {
             Tab::Buttons => self.tab_buttons(ui),
             Tab::Sticks => self.tab_sticks(ui),
             Tab::Triggers => self.tab_triggers(ui),
+            Tab::Mappings => self.tab_mappings(ui),
+            Tab::Chords => self.tab_chords(ui),
+            Tab::Layers => self.tab_layers(ui),
+            Tab::Macros => self.tab_macros(ui),
+            Tab::Flick => self.tab_flick(ui),
+            Tab::Gyro => self.tab_gyro(ui),
+            Tab::Tilt => self.tab_tilt(ui),
 
             Tab::Load => self.tab_load(ui),
             Tab::Save => self.tab_save(ui),
@@ -137,6 +164,13 @@ enum Tab {
     Buttons,
     Sticks,
     Triggers,
+    Mappings,
+    Chords,
+    Layers,
+    Macros,
+    Flick,
+    Gyro,
+    Tilt,
     Save,
     Load,
 }
@@ -148,6 +182,13 @@ impl Tab {
             Tab::Buttons => "Buttons",
             Tab::Sticks => "Sticks",
             Tab::Triggers => "Triggers",
+            Tab::Mappings => "Mappings",
+            Tab::Chords => "Chords",
+            Tab::Layers => "Layers",
+            Tab::Macros => "Macros",
+            Tab::Flick => "Flick Stick",
+            Tab::Gyro => "Gyro",
+            Tab::Tilt => "Tilt",
             Tab::Save => "Save",
             Tab::Load => "Load",
         }
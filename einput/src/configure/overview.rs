@@ -23,7 +23,11 @@ impl Configure {
 
                 ui.label(RichText::new("Buttons").strong());
 
-                ui.add(ButtonsInput::new(*buttons).available(self.device.info().input.buttons));
+                ui.add(
+                    ButtonsInput::new(*buttons)
+                        .available(self.device.info().input.buttons)
+                        .latched(self.config.input.latched.buttons()),
+                );
             }
 
             if self
@@ -60,7 +64,10 @@ impl Configure {
 
                 ui.horizontal_wrapped(|ui| {
                     for id in TriggerId::ALL {
-                        ui.add(TriggerInput::new(*triggers.get(id), format!("{id:?}")));
+                        ui.add(
+                            TriggerInput::new(*triggers.get(id), format!("{id:?}"))
+                                .latched(self.config.input.latched.trigger(id)),
+                        );
                     }
                 });
             }
@@ -0,0 +1,90 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui::{ProgressBar, RichText, Slider, Ui};
+use einput_config::input::RestSampler;
+use einput_util::axis::{Stick, Trigger};
+
+/// How long an input is sampled for while left untouched.
+const DURATION: Duration = Duration::from_secs(3);
+
+/// Samples a stick or trigger at rest and suggests a deadzone from it.
+pub struct RestCalibration<Id> {
+    pub id: Id,
+    start: Instant,
+    sampler: RestSampler,
+    suggestion: Option<f32>,
+}
+
+pub enum RestAction {
+    None,
+    Accept(f32),
+    Cancel,
+}
+
+impl<Id> RestCalibration<Id> {
+    pub fn new(id: Id) -> Self {
+        RestCalibration {
+            id,
+            start: Instant::now(),
+            sampler: RestSampler::new(),
+            suggestion: None,
+        }
+    }
+
+    pub fn add(&mut self, stick: Stick) {
+        if self.suggestion.is_none() {
+            self.sampler.add(stick);
+        }
+    }
+
+    pub fn add_trigger(&mut self, trigger: Trigger) {
+        if self.suggestion.is_none() {
+            self.sampler.add_trigger(trigger);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) -> RestAction {
+        let elapsed = self.start.elapsed();
+
+        if elapsed < DURATION {
+            ui.label("Don't touch the input");
+            ui.add(ProgressBar::new(elapsed.as_secs_f32() / DURATION.as_secs_f32()));
+
+            return match ui.button("Cancel").clicked() {
+                true => RestAction::Cancel,
+                false => RestAction::None,
+            };
+        }
+
+        let Some(stats) = self.sampler.stats()
+        else { return RestAction::Cancel };
+
+        let suggestion = self
+            .suggestion
+            .get_or_insert_with(|| stats.suggested_deadzone());
+
+        ui.label(
+            RichText::new(format!(
+                "Drift: {:.03}, Noise: {:.03}, Max: {:.03}",
+                stats.drift, stats.noise, stats.max
+            ))
+            .monospace(),
+        );
+
+        ui.add(Slider::new(suggestion, 0.0..=1.0).text("Suggested"));
+
+        let mut action = RestAction::None;
+
+        ui.horizontal(|ui| {
+            if ui.button("Accept").clicked() {
+                action = RestAction::Accept(*suggestion);
+            }
+
+            if ui.button("Cancel").clicked() {
+                action = RestAction::Cancel;
+            }
+        });
+
+        action
+    }
+}
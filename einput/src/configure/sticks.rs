@@ -1,19 +1,28 @@
 use std::f32::consts::PI;
 
-use eframe::egui::{self, Color32, RichText, Sense, Slider, Stroke, Ui, Vec2};
-use einput_config::input::{StickSampler, StickConfig};
+use eframe::egui::{self, Color32, Rect, RichText, Rounding, Sense, Slider, Stroke, Ui, Vec2};
+use einput_config::input::{DeadzoneMode, ShapeMapping, StickSampler, StickConfig};
 use einput_device::input:: sticks::StickId;
 use einput_util::axis::Stick;
 
-use super::Configure;
+use super::{
+    curve::curve_ui,
+    rest::{RestAction, RestCalibration},
+    Configure,
+};
 
 #[derive(Default)]
 pub struct SticksTab {
     calibrating: Option<Calibration>,
+    resting: Option<RestCalibration<StickId>>,
 }
 
 impl Configure {
     pub fn tab_sticks(&mut self, ui: &mut Ui) {
+        if ui.checkbox(&mut self.config.input.swap_sticks, "Swap Sticks").changed() {
+            self.update_config();
+        }
+
         ui.horizontal(|ui| {
             let Some(&raw_sticks) = self.get_raw_input().and_then(|input| input.sticks())
             else { return };
@@ -23,7 +32,11 @@ impl Configure {
 
             for id in StickId::ALL {
                 let raw_stick = *raw_sticks.get(id);
-                let stick = *sticks.get(id);
+                let stick = match (self.config.input.swap_sticks, id) {
+                    (false, id) => *sticks.get(id),
+                    (true, StickId::Left) => sticks.right,
+                    (true, StickId::Right) => sticks.left,
+                };
 
                 ui.group(|ui| {
                     ui.vertical(|ui| {
@@ -31,13 +44,28 @@ impl Configure {
 
                         if let Some(calibration) = &mut self.tab_sticks.calibrating {
                             if calibration.id == id {
-                                config.samples = Some(calibration.sampler.samples);
-                                if calibration.id == id {
-                                    calibration.sampler.add(raw_stick);
+                                match calibration.step {
+                                    CalibrationStep::Center => {
+                                        calibration.sampler.add_center(raw_stick);
+                                        config.center = calibration.sampler.center();
+                                    }
+                                    CalibrationStep::Range => {
+                                        calibration.sampler.add(raw_stick);
+                                        config.samples = Some(calibration.sampler.samples);
+                                    }
                                 }
                             }
                         }
 
+                        if let Some(resting) = &mut self.tab_sticks.resting {
+                            if resting.id == id {
+                                resting.add(Stick {
+                                    x: raw_stick.x - config.center[0],
+                                    y: raw_stick.y - config.center[1],
+                                });
+                            }
+                        }
+
                         ui.label(format!("{id:?}"));
 
                         ui.label(
@@ -65,26 +93,168 @@ impl Configure {
 
                         ui.add_space(2.0);
 
-                        if ui
-                            .add(Slider::new(&mut config.deadzone, 0.0..=1.0).text("Deadzone"))
-                            .changed()
-                        {
+                        let mut changed = false;
+
+                        ui.horizontal(|ui| {
+                            for mode in DeadzoneMode::ALL {
+                                changed |= ui
+                                    .radio_value(&mut config.deadzone_mode, mode, mode.name())
+                                    .changed();
+                            }
+                        });
+
+                        if config.deadzone_mode == DeadzoneMode::Axial {
+                            let [x, y] = &mut config.axis_deadzones;
+                            changed |= ui.add(Slider::new(x, 0.0..=1.0).text("X Deadzone")).changed();
+                            changed |= ui.add(Slider::new(y, 0.0..=1.0).text("Y Deadzone")).changed();
+                        } else {
+                            changed |= ui
+                                .add(Slider::new(&mut config.deadzone, 0.0..=1.0).text("Deadzone"))
+                                .changed();
+                        }
+
+                        changed |= ui
+                            .add(Slider::new(&mut config.outer_deadzone, 0.0..=1.0).text("Outer Deadzone"))
+                            .changed();
+                        changed |= ui
+                            .add(Slider::new(&mut config.anti_deadzone, 0.0..=1.0).text("Anti-Deadzone"))
+                            .changed();
+
+                        match &mut self.tab_sticks.resting {
+                            None => {
+                                if ui.button("Suggest Deadzone").clicked() {
+                                    self.tab_sticks.resting = Some(RestCalibration::new(id));
+                                }
+                            }
+                            Some(resting) if resting.id == id => match resting.show(ui) {
+                                RestAction::None => {}
+                                RestAction::Accept(deadzone) => {
+                                    config.deadzone = deadzone;
+                                    config.axis_deadzones = [deadzone; 2];
+                                    self.tab_sticks.resting = None;
+                                    changed = true;
+                                }
+                                RestAction::Cancel => self.tab_sticks.resting = None,
+                            },
+                            Some(_) => {
+                                ui.add_enabled(false, egui::Button::new("Suggest Deadzone"));
+                            }
+                        }
+
+                        ui.push_id(id, |ui| {
+                            ui.collapsing("Geometry", |ui| {
+                                let [invert_x, invert_y] = &mut config.invert;
+                                let [scale_x, scale_y] = &mut config.scale;
+
+                                ui.horizontal(|ui| {
+                                    changed |= ui.checkbox(invert_x, "Invert X").changed();
+                                    changed |= ui.checkbox(invert_y, "Invert Y").changed();
+                                    changed |= ui.checkbox(&mut config.swap_axes, "Swap Axes").changed();
+                                });
+
+                                changed |= ui
+                                    .add(Slider::new(&mut config.rotation, -180.0..=180.0).text("Rotation").suffix("°"))
+                                    .changed();
+                                changed |= ui.add(Slider::new(scale_x, 0.0..=2.0).text("X Scale")).changed();
+                                changed |= ui.add(Slider::new(scale_y, 0.0..=2.0).text("Y Scale")).changed();
+
+                                ui.horizontal(|ui| {
+                                    for shape in ShapeMapping::ALL {
+                                        changed |= ui.radio_value(&mut config.shape, shape, shape.name()).changed();
+                                    }
+                                });
+                            });
+
+                            ui.collapsing("Curves", |ui| {
+                                let [x_curve, y_curve] = &mut config.axis_curves;
+
+                                changed |= curve_ui(ui, "Radius", &mut config.curve, raw_stick.length(), stick.length());
+                                changed |= curve_ui(ui, "X", x_curve, raw_stick.x.abs(), stick.x.abs());
+                                changed |= curve_ui(ui, "Y", y_curve, raw_stick.y.abs(), stick.y.abs());
+                            });
+                        });
+
+                        if let Some(notches) = config.notches {
+                            ui.label(format!(
+                                "Notches: {}",
+                                notches.map(|notch| format!("{:.0}°", notch.to_degrees())).join(", "),
+                            ));
+
+                            changed |= ui
+                                .add(Slider::new(&mut config.notch_snap, 0.0..=10.0).text("Notch Snap").suffix("°"))
+                                .changed();
+
+                            if ui.button("Clear Notches").clicked() {
+                                config.notches = None;
+                                changed = true;
+                            }
+                        }
+
+                        ui.label(format!("Center: X: {:+.03}, Y: {:+.03}", config.center[0], config.center[1]));
+
+                        if changed {
                             self.update_config();
                         }
 
-                        if self.tab_sticks.calibrating.is_none() {
-                            if ui.button("Calibrate").clicked() {
-                                self.tab_sticks.calibrating = Some(Calibration::new(id));
+                        match &mut self.tab_sticks.calibrating {
+                            None => {
+                                if ui.button("Calibrate").clicked() {
+                                    self.tab_sticks.calibrating = Some(Calibration::new(id));
+                                }
                             }
-                        } else if self.tab_sticks.calibrating.as_ref().unwrap().id == id {
-                            if ui.button("Finish Calibration").clicked() {
-                                let samples =
-                                    self.tab_sticks.calibrating.take().unwrap().sampler.samples;
-                                self.config.input.sticks[id as usize].samples = Some(samples);
-                                self.update_config();
+                            Some(calibration) if calibration.id == id => match calibration.step {
+                                CalibrationStep::Center => {
+                                    ui.label("Leave the stick at rest");
+
+                                    ui.checkbox(&mut calibration.notches, "Capture Notches");
+
+                                    if ui.button("Next").clicked() {
+                                        calibration.step = CalibrationStep::Range;
+                                    }
+                                }
+                                CalibrationStep::Range => {
+                                    if calibration.notches {
+                                        ui.label("Rotate the stick along its edges, pushing into each notch");
+                                    } else {
+                                        ui.label("Rotate the stick along its edges");
+                                    }
+
+                                    if calibration.missing_notches {
+                                        let missing: Vec<&str> =
+                                            calibration.sampler.missing_notches().map(|i| NOTCH_NAMES[i]).collect();
+
+                                        ui.colored_label(
+                                            Color32::RED,
+                                            format!("Notches not reached yet: {}", missing.join(", ")),
+                                        );
+                                        ui.checkbox(&mut calibration.notches, "Capture Notches");
+                                    }
+
+                                    if ui.button("Finish Calibration").clicked() {
+                                        let notches = calibration.sampler.notches();
+
+                                        // Keeps sampling until every notch is reached or capturing them is turned off
+                                        if calibration.notches && notches.is_none() {
+                                            calibration.missing_notches = true;
+                                        } else {
+                                            let calibration = self.tab_sticks.calibrating.take().unwrap();
+                                            let config = &mut self.config.input.sticks[id as usize];
+
+                                            config.center = calibration.sampler.center();
+                                            config.samples = Some(calibration.sampler.samples);
+
+                                            if calibration.notches {
+                                                config.notches = notches;
+                                            }
+
+                                            self.update_config();
+                                        }
+                                    }
+                                }
+                            },
+                            Some(_) => {
+                                ui.add_enabled(false, egui::Button::new("Calibrate"));
                             }
-                        } else {
-                            ui.add_enabled(false, egui::Button::new("Calibrate"));
                         }
                     });
                 });
@@ -153,13 +323,51 @@ fn draw_stick(ui: &mut Ui, raw_stick: Stick, stick: Stick, config: &StickConfig)
         }
     }
 
+    // Outer Deadzone
+    if config.outer_deadzone > 0.0 {
+        ui.painter().circle(
+            rect.center(),
+            radius * (1.0 - config.outer_deadzone).max(0.0),
+            Color32::TRANSPARENT,
+            Stroke::new(1.0, ui.visuals().error_fg_color),
+        );
+    }
+
     // Deadzone
-    ui.painter().circle(
-        rect.center(),
-        radius * config.deadzone,
-        ui.visuals().faint_bg_color,
-        Stroke::new(1.0, ui.visuals().error_fg_color),
-    );
+    if config.deadzone_mode == DeadzoneMode::Axial {
+        let [x, y] = config.axis_deadzones;
+        let stroke = Stroke::new(1.0, ui.visuals().error_fg_color);
+
+        ui.painter().rect(
+            Rect::from_center_size(rect.center(), Vec2::new(x * 2.0, 2.0) * radius),
+            Rounding::ZERO,
+            ui.visuals().faint_bg_color,
+            stroke,
+        );
+        ui.painter().rect(
+            Rect::from_center_size(rect.center(), Vec2::new(2.0, y * 2.0) * radius),
+            Rounding::ZERO,
+            ui.visuals().faint_bg_color,
+            stroke,
+        );
+    } else {
+        ui.painter().circle(
+            rect.center(),
+            radius * config.deadzone,
+            ui.visuals().faint_bg_color,
+            Stroke::new(1.0, ui.visuals().error_fg_color),
+        );
+    }
+
+    // Anti-Deadzone
+    if config.anti_deadzone > 0.0 {
+        ui.painter().circle(
+            rect.center(),
+            radius * config.anti_deadzone,
+            Color32::TRANSPARENT,
+            Stroke::new(1.0, ui.visuals().hyperlink_color),
+        );
+    }
 
     // Middle Dot
     ui.painter().circle(
@@ -186,8 +394,24 @@ fn draw_stick(ui: &mut Ui, raw_stick: Stick, stick: Stick, config: &StickConfig)
     );
 }
 
+/// Notch directions, starting from the right and going clockwise.
+const NOTCH_NAMES: [&str; 8] = [
+    "Right",
+    "Down-Right",
+    "Down",
+    "Down-Left",
+    "Left",
+    "Up-Left",
+    "Up",
+    "Up-Right",
+];
+
 struct Calibration {
     id: StickId,
+    step: CalibrationStep,
+    notches: bool,
+    /// Set when finishing failed because some notches weren't reached.
+    missing_notches: bool,
     sampler: StickSampler,
 }
 
@@ -195,7 +419,15 @@ impl Calibration {
     fn new(id: StickId) -> Self {
         Calibration {
             id,
+            step: CalibrationStep::Center,
+            notches: false,
+            missing_notches: false,
             sampler: StickSampler::new(),
         }
     }
 }
+
+enum CalibrationStep {
+    Center,
+    Range,
+}
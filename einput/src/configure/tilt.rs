@@ -0,0 +1,133 @@
+use eframe::egui::{ComboBox, RichText, Slider, Ui};
+use einput_config::tilt::{self, Tilt, TiltAngle, TiltAxis, TiltSource};
+use einput_device::input::{buttons::Button, sticks::StickId};
+
+use crate::widgets::stick_input::StickInput;
+
+use super::{curve::curve_ui, Configure};
+
+impl Configure {
+    pub fn tab_tilt(&mut self, ui: &mut Ui) {
+        let Some(raw) = self.raw_reader.current().values().next()
+        else { return };
+
+        if let Some(orientation) = raw.orientation() {
+            let [w, x, y, z] = orientation.quaternion;
+            let [gx, gy, gz] = orientation.gravity;
+
+            ui.label(RichText::new(format!("Orientation: {w:>6.03} {x:>6.03} {y:>6.03} {z:>6.03}")).monospace());
+            ui.label(RichText::new(format!("Gravity:     {gx:>6.03} {gy:>6.03} {gz:>6.03}")).monospace());
+        }
+
+        let source = self.config.tilt.as_ref().map_or(TiltSource::Accelerometer, |tilt| tilt.source);
+
+        let Some(angles) = source.gravity(raw).map(tilt::angles)
+        else {
+            ui.label("This device has no accelerometer");
+            return;
+        };
+
+        ui.label(RichText::new(format!("Roll: {:>7.02}°, Pitch: {:>7.02}°", angles[0], angles[1])).monospace());
+
+        let mut enabled = self.config.tilt.is_some();
+
+        if ui.checkbox(&mut enabled, "Tilt to Stick").changed() {
+            self.config.tilt = enabled.then(Tilt::default);
+            self.update_config();
+        }
+
+        let Some(tilt) = &mut self.config.tilt
+        else { return };
+
+        let stick = self
+            .reader
+            .current()
+            .values()
+            .next()
+            .and_then(|input| input.get(tilt.stick))
+            .copied();
+
+        if let Some(stick) = stick {
+            ui.add(StickInput::new(stick, format!("{:?}", tilt.stick)));
+        }
+
+        ui.separator();
+
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            for id in StickId::ALL {
+                changed |= ui.radio_value(&mut tilt.stick, id, id.name()).changed();
+            }
+
+            ui.separator();
+
+            ComboBox::from_id_source("tilt_recenter")
+                .selected_text(tilt.recenter.map_or("None".to_owned(), |button| format!("{button:?}")))
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut tilt.recenter, None, "None").changed();
+
+                    for button in Button::ALL {
+                        changed |= ui.selectable_value(&mut tilt.recenter, Some(button), format!("{button:?}")).changed();
+                    }
+                });
+
+            ui.label("Recenter");
+        });
+
+        ui.horizontal(|ui| {
+            for source in TiltSource::ALL {
+                changed |= ui.radio_value(&mut tilt.source, source, source.name()).changed();
+            }
+        });
+
+        for (name, axis) in ["X", "Y"].into_iter().zip(&mut tilt.axes) {
+            ui.separator();
+
+            ui.push_id(name, |ui| {
+                changed |= axis_ui(ui, name, axis, angles);
+            });
+        }
+
+        if changed {
+            self.update_config();
+        }
+    }
+}
+
+/// Returns true if the axis was changed
+fn axis_ui(ui: &mut Ui, name: &str, axis: &mut Option<TiltAxis>, angles: [f32; 2]) -> bool {
+    let mut changed = false;
+    let mut enabled = axis.is_some();
+
+    if ui.checkbox(&mut enabled, name).changed() {
+        *axis = enabled.then(TiltAxis::default);
+        changed = true;
+    }
+
+    let Some(axis) = axis
+    else { return changed };
+
+    ui.horizontal(|ui| {
+        for angle in TiltAngle::ALL {
+            changed |= ui.radio_value(&mut axis.angle, angle, angle.name()).changed();
+        }
+
+        changed |= ui.checkbox(&mut axis.invert, "Invert").changed();
+    });
+
+    changed |= ui.add(Slider::new(&mut axis.max_angle, 5.0..=90.0).text("Max Angle").suffix("°")).changed();
+    changed |= ui.add(Slider::new(&mut axis.deadzone, 0.0..=30.0).text("Deadzone").suffix("°")).changed();
+
+    // Shown against the absolute angle, since the center is only known while running
+    let angle = match axis.angle {
+        TiltAngle::Roll => angles[0],
+        TiltAngle::Pitch => angles[1],
+    };
+    let raw = ((angle.abs() - axis.deadzone) / (axis.max_angle - axis.deadzone)).clamp(0.0, 1.0);
+    let configured = axis.curve.apply(raw);
+
+    changed |= curve_ui(ui, &format!("{name} Curve"), &mut axis.curve, raw, configured);
+
+    changed
+}
@@ -1,11 +1,22 @@
 use eframe::egui::{
-    Color32, CursorIcon, Rect, Rounding, Sense, Stroke, TextStyle, Ui, Vec2, WidgetText,
+    self, Color32, CursorIcon, Rect, RichText, Rounding, Sense, Stroke, TextStyle, Ui, Vec2,
+    WidgetText,
 };
-use einput_config::input::TriggerConfig;
+use einput_config::input::{TriggerConfig, TriggerSampler};
 use einput_device::input::triggers::TriggerId;
 use einput_util::axis::{Trigger, TriggerAxis};
 
-use super::Configure;
+use super::{
+    curve::curve_ui,
+    rest::{RestAction, RestCalibration},
+    Configure,
+};
+
+#[derive(Default)]
+pub struct TriggersTab {
+    calibrating: Option<Calibration>,
+    resting: Option<RestCalibration<TriggerId>>,
+}
 
 impl Configure {
     pub fn tab_triggers(&mut self, ui: &mut Ui) {
@@ -22,6 +33,105 @@ impl Configure {
                 }
             }
         });
+
+        ui.horizontal_wrapped(|ui| {
+            for id in TriggerId::ALL {
+                if ui.checkbox(&mut self.config.input.triggers[id as usize].latch, format!("Latch {id:?}")).changed() {
+                    self.update_config();
+                }
+            }
+        });
+
+        ui.collapsing("Curves", |ui| {
+            for id in TriggerId::ALL {
+                let config = &mut self.config.input.triggers[id as usize];
+
+                let raw = triggers.get(id).0.to_f32();
+                let min = config.min.to_f32();
+                let max = config.max.to_f32();
+                let linear = if max > min { ((raw - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+                let configured = config.curve.apply(linear);
+
+                if curve_ui(ui, &format!("{id:?}"), &mut config.curve, linear, configured) {
+                    self.update_config();
+                }
+            }
+        });
+
+        ui.separator();
+
+        match &mut self.tab_triggers.calibrating {
+            None => {
+                ui.horizontal_wrapped(|ui| {
+                    for id in TriggerId::ALL {
+                        if ui.button(format!("Calibrate {id:?}")).clicked() {
+                            self.tab_triggers.calibrating = Some(Calibration::new(id));
+                        }
+                    }
+                });
+            }
+            Some(calibration) => {
+                let id = calibration.id;
+                calibration.sampler.add(*triggers.get(id));
+
+                ui.label(format!("Release {id:?}, then press it all the way a few times"));
+
+                let range = calibration.sampler.range();
+
+                if let Some((min, max)) = range {
+                    ui.label(RichText::new(format!("Min: {:.02}, Max: {:.02}", min.to_f32(), max.to_f32())).monospace());
+                }
+
+                let (mut finish, mut cancel) = (false, false);
+
+                ui.horizontal(|ui| {
+                    finish = ui.add_enabled(range.is_some(), egui::Button::new("Finish Calibration")).clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+
+                if finish {
+                    if let Some((min, max)) = range {
+                        self.config.input.triggers[id as usize].set_range(min, max);
+                        self.update_config();
+                    }
+                }
+
+                if finish || cancel {
+                    self.tab_triggers.calibrating = None;
+                }
+            }
+        }
+
+        ui.separator();
+
+        match &mut self.tab_triggers.resting {
+            None => {
+                ui.horizontal_wrapped(|ui| {
+                    for id in TriggerId::ALL {
+                        if ui.button(format!("Suggest {id:?} Minimum")).clicked() {
+                            self.tab_triggers.resting = Some(RestCalibration::new(id));
+                        }
+                    }
+                });
+            }
+            Some(resting) => {
+                let id = resting.id;
+                resting.add_trigger(*triggers.get(id));
+
+                ui.label(format!("{id:?} Minimum"));
+
+                match resting.show(ui) {
+                    RestAction::None => {}
+                    RestAction::Accept(min) => {
+                        let config = &mut self.config.input.triggers[id as usize];
+                        config.set_range(u8::from_f32(min), config.max);
+                        self.tab_triggers.resting = None;
+                        self.update_config();
+                    }
+                    RestAction::Cancel => self.tab_triggers.resting = None,
+                }
+            }
+        }
     }
 }
 
@@ -155,7 +265,7 @@ fn draw_trigger(ui: &mut Ui, id: TriggerId, trigger: Trigger, config: &mut Trigg
         .x;
     if min_delta != 0.0 {
         min += min_delta / 250.0;
-        config.min = (min * 255.0) as u8;
+        config.set_range((min * 255.0) as u8, config.max);
         changed = true;
     }
 
@@ -166,7 +276,7 @@ fn draw_trigger(ui: &mut Ui, id: TriggerId, trigger: Trigger, config: &mut Trigg
         .x;
     if max_delta != 0.0 {
         max += max_delta / 250.0;
-        config.max = (max * 255.0) as u8;
+        config.set_range(config.min, (max * 255.0) as u8);
         changed = true;
     }
 
@@ -176,3 +286,17 @@ fn draw_trigger(ui: &mut Ui, id: TriggerId, trigger: Trigger, config: &mut Trigg
 
     changed
 }
+
+struct Calibration {
+    id: TriggerId,
+    sampler: TriggerSampler,
+}
+
+impl Calibration {
+    fn new(id: TriggerId) -> Self {
+        Calibration {
+            id,
+            sampler: TriggerSampler::new(),
+        }
+    }
+}
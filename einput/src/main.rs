@@ -13,6 +13,7 @@ use eframe::{
     egui::{Context, Id, ViewportBuilder, ViewportCommand, ViewportId},
     CreationContext, NativeOptions,
 };
+use dsu::server::ServerConfig;
 use einput_config::DeviceConfig;
 use einput_core::{
     device::{Device, DeviceReader},
@@ -20,11 +21,12 @@ use einput_core::{
     EInput,
 };
 use einput_device::DeviceId;
+use einput_dsu::output::DsuConfig;
 use log::error;
 use serde::{Deserialize, Serialize};
 use simple_logger::SimpleLogger;
 
-use self::configure::Configure;
+use self::{configure::Configure, outputs::DsuSettings};
 
 mod configure;
 mod devices;
@@ -63,6 +65,7 @@ struct App {
     tracking_order: Vec<DeviceId>,
     reader: DeviceReader,
     outputs: HashMap<String, OutputData>,
+    dsu: DsuSettings,
 
     configuring: Vec<ConfigureState>,
 
@@ -84,8 +87,20 @@ impl App {
         let einput = EInput::new();
         configs.set_to_last(&einput);
         einput_driver_gc::start(einput.clone());
+        #[cfg(target_os = "linux")]
+        einput_driver_evdev::start(einput.clone());
 
-        let mut outputs: HashMap<String, OutputData> = outputs::all()
+        let dsu_config: ServerConfig =
+            match serde_json::from_str(storage.get_string("dsu").as_deref().unwrap_or("")) {
+                Ok(config) => config,
+                Err(e) => {
+                    error!("error loading dsu config: {e}");
+                    ServerConfig::default()
+                }
+            };
+        let dsu_config = DsuConfig::new(dsu_config);
+
+        let mut outputs: HashMap<String, OutputData> = outputs::all(&dsu_config)
             .into_iter()
             .map(|(id, out)| (id, OutputData::new(out)))
             .collect();
@@ -110,6 +125,7 @@ impl App {
 
         App {
             outputs,
+            dsu: DsuSettings::new(dsu_config),
             einput,
             last_refresh: Instant::now(),
             tracking: HashMap::new(),
@@ -193,6 +209,15 @@ impl eframe::App for App {
             }
         };
 
+        match serde_json::to_string::<ServerConfig>(&self.dsu.config()) {
+            Ok(string) => {
+                storage.set_string("dsu", string);
+            }
+            Err(e) => {
+                error!("error serializing dsu config: {e}");
+            }
+        }
+
         let output_map = self
             .outputs
             .iter()
@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-use eframe::egui::{self, Context, RichText, ScrollArea};
+use dsu::server::{ServerConfig, Subnet};
+use eframe::egui::{self, CollapsingHeader, Color32, Context, DragValue, RichText, ScrollArea, Ui};
 use einput_core::output::Output;
+use einput_dsu::output::{DsuConfig, DsuOutput};
 
 use crate::{
     widgets::device_selector::{DeviceSelector, PickState},
@@ -9,16 +11,21 @@ use crate::{
 };
 
 #[allow(unused_variables, unused_mut)]
-pub fn all() -> HashMap<String, Box<dyn Output>> {
+pub fn all(dsu: &DsuConfig) -> HashMap<String, Box<dyn Output>> {
     let mut outputs = HashMap::new();
 
-    outputs.insert("dsu".to_owned(), Box::new(einput_dsu::output::DsuOutput::new()) as _);
+    outputs.insert("dsu".to_owned(), Box::new(DsuOutput::with_shared_config(dsu.clone())) as _);
 
     #[cfg(windows)]
     {
         outputs.insert("vigem".to_owned(), Box::new(einput_output_vigem::XboxOutput::new()) as _);
     }
 
+    #[cfg(target_os = "linux")]
+    {
+        outputs.insert("uinput".to_owned(), Box::new(einput_output_uinput::UinputOutput::new()) as _);
+    }
+
     outputs
 }
 
@@ -36,6 +43,10 @@ impl App {
                             ui.vertical(|ui| {
                                 ui.label(RichText::new(output.output.name()).strong());
 
+                                if output.output.name() == "dsu" {
+                                    self.dsu.show(ui);
+                                }
+
                                 for i in 0..output.devices.len() {
                                     let device = &output.devices[i];
 
@@ -77,3 +88,111 @@ impl App {
         });
     }
 }
+
+/// Edits the DSU server's allowed addresses, client limit and send rate.
+pub struct DsuSettings {
+    config: DsuConfig,
+    allow: String,
+    max_clients: usize,
+    /// Milliseconds
+    min_send_interval: u64,
+    /// Milliseconds
+    keepalive_interval: u64,
+    error: Option<String>,
+}
+
+impl DsuSettings {
+    pub fn new(config: DsuConfig) -> Self {
+        let current = config.get();
+
+        Self {
+            config,
+            allow: current.allow.iter().map(Subnet::to_string).collect::<Vec<_>>().join(", "),
+            max_clients: current.max_clients,
+            min_send_interval: current.min_send_interval.as_millis() as u64,
+            keepalive_interval: current.keepalive_interval.as_millis() as u64,
+            error: None,
+        }
+    }
+
+    pub fn config(&self) -> ServerConfig {
+        self.config.get()
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        CollapsingHeader::new("Settings")
+            .id_source("dsu_settings")
+            .show(ui, |ui| {
+                ui.label("Allowed Addresses");
+                ui.text_edit_singleline(&mut self.allow)
+                    .on_hover_text("Comma separated, like 127.0.0.1, 192.168.1.0/24. Empty allows any address.");
+
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.max_clients).clamp_range(1..=64));
+                    ui.label("Max Clients");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut self.min_send_interval)
+                            .clamp_range(1..=1000)
+                            .suffix(" ms"),
+                    );
+                    ui.label("Min Send Interval")
+                        .on_hover_text("Minimum time between two packets for the same controller");
+                });
+
+                let max_keepalive_interval = self.max_keepalive_interval();
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut self.keepalive_interval)
+                            .clamp_range(1..=max_keepalive_interval)
+                            .suffix(" ms"),
+                    );
+                    ui.label("Keepalive Interval")
+                        .on_hover_text("Time after which an unchanged controller is sent again");
+                });
+
+                if ui.button("Apply").clicked() {
+                    self.apply();
+                }
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(Color32::RED, error);
+                }
+            });
+    }
+
+    /// Clients time out without keepalive packets, so they have to be sent more often than the timeout.
+    fn max_keepalive_interval(&self) -> u64 {
+        (self.config.get().timeout.as_millis() as u64).saturating_sub(1).max(1)
+    }
+
+    fn apply(&mut self) {
+        let allow = self
+            .allow
+            .split(',')
+            .map(str::trim)
+            .filter(|subnet| !subnet.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Subnet>, _>>();
+
+        match allow {
+            Ok(allow) => {
+                self.min_send_interval = self.min_send_interval.max(1);
+                self.keepalive_interval = self.keepalive_interval.clamp(1, self.max_keepalive_interval());
+
+                self.config.set(ServerConfig {
+                    allow,
+                    max_clients: self.max_clients,
+                    min_send_interval: Duration::from_millis(self.min_send_interval),
+                    keepalive_interval: Duration::from_millis(self.keepalive_interval),
+                    ..self.config.get()
+                });
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+}
@@ -6,6 +6,7 @@ use einput_device::input::buttons::{Button, Buttons};
 pub struct ButtonsInput {
     available: Buttons,
     buttons: Buttons,
+    latched: Buttons,
 }
 
 impl ButtonsInput {
@@ -13,6 +14,7 @@ impl ButtonsInput {
         Self {
             available: Buttons::ALL,
             buttons,
+            latched: Buttons::default(),
         }
     }
 
@@ -20,6 +22,12 @@ impl ButtonsInput {
         self.available = buttons;
         self
     }
+
+    /// Buttons held down by a latch, which are highlighted
+    pub fn latched(mut self, buttons: Buttons) -> Self {
+        self.latched = buttons;
+        self
+    }
 }
 
 impl Widget for ButtonsInput {
@@ -66,12 +74,13 @@ impl Widget for ButtonsInput {
                             Stroke::new(1.0, ui.visuals().widgets.inactive.bg_stroke.color)
                         };
 
-                        ui.painter().rect(
-                            rect,
-                            Rounding::same(1.0),
-                            ui.visuals().widgets.inactive.bg_fill,
-                            stroke,
-                        );
+                        let fill = if self.latched.is_pressed(button) {
+                            ui.visuals().selection.bg_fill
+                        } else {
+                            ui.visuals().widgets.inactive.bg_fill
+                        };
+
+                        ui.painter().rect(rect, Rounding::same(1.0), fill, stroke);
 
                         let text_color = if self.available.is_pressed(button) {
                             ui.visuals().strong_text_color()
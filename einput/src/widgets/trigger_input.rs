@@ -6,6 +6,7 @@ use einput_util::axis::Trigger;
 pub struct TriggerInput {
     trigger: Trigger,
     label: WidgetText,
+    latched: bool,
 }
 
 impl TriggerInput {
@@ -13,8 +14,15 @@ impl TriggerInput {
         TriggerInput {
             trigger,
             label: label.into(),
+            latched: false,
         }
     }
+
+    /// Highlights the trigger as held down by a latch
+    pub fn latched(mut self, latched: bool) -> Self {
+        self.latched = latched;
+        self
+    }
 }
 
 impl Widget for TriggerInput {
@@ -49,13 +57,19 @@ impl Widget for TriggerInput {
         let trig_rect =
             Rect::from_min_max(rect.min + Vec2::new(0.0, margin + text_height), rect.max);
 
+        let fill = if self.latched {
+            ui.visuals().selection.bg_fill
+        } else {
+            ui.visuals().widgets.inactive.bg_fill
+        };
+
         ui.painter().rect(
             Rect::from_min_size(
                 trig_rect.min,
                 Vec2::new(trig_rect.width() * percent, trig_rect.height()),
             ),
             Rounding::ZERO,
-            ui.visuals().widgets.inactive.bg_fill,
+            fill,
             Stroke::NONE,
         );
 
@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use einput_device::input::buttons::{Button, Buttons};
+use serde::{Deserialize, Serialize};
+
+use crate::input::{ButtonAction, ButtonTargets};
+
+/// Presses `output` while all of `buttons` are held.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Chord {
+    /// Source buttons that make up the chord.
+    pub buttons: Vec<Button>,
+    pub output: ButtonTargets,
+    /// Milliseconds between the first and last button press, or 0 for no limit.
+    pub window: u32,
+    /// Hides the chord's buttons while it's held, and holds them back for `window` while it might be starting.
+    /// Off by default, since it delays every press of the chord's buttons.
+    pub suppress: bool,
+}
+
+impl Default for Chord {
+    fn default() -> Self {
+        Self {
+            buttons: Vec::new(),
+            output: ButtonTargets::default(),
+            window: 200,
+            suppress: false,
+        }
+    }
+}
+
+impl Chord {
+    pub fn components(&self) -> Buttons {
+        self.buttons.iter().fold(Buttons::default(), |buttons, &button| buttons | button)
+    }
+
+    fn window(&self) -> Option<Duration> {
+        (self.window > 0).then(|| Duration::from_millis(self.window as u64))
+    }
+
+    /// Returns true if the chord holds back its buttons, which then have to be released over time.
+    pub(super) fn needs_tick(&self) -> bool {
+        self.suppress && self.window > 0
+    }
+}
+
+/// How long held back buttons are pressed for, when they're released before the window ends.
+const TAP_DURATION: Duration = Duration::from_millis(50);
+
+/// Holds back the components of a button combo while it might be starting,
+/// and replays them as a tap if they're released before the combo completes.
+#[derive(Clone, Debug, Default)]
+pub(super) struct HoldBack {
+    /// When the first component was pressed, since all of them were released.
+    first: Option<Instant>,
+    /// Set once the combo completes, until all of its components are released.
+    consumed: bool,
+    /// Components held back while the combo might be starting.
+    held_back: Buttons,
+    /// Held back components that were released too early, and when.
+    tap: Option<(Buttons, Instant)>,
+}
+
+impl HoldBack {
+    /// Returns the held back components still being replayed as a tap.
+    pub(super) fn tapped(&mut self, now: Instant) -> Buttons {
+        match self.tap {
+            Some((buttons, start)) if now - start < TAP_DURATION => buttons,
+            _ => {
+                self.tap = None;
+                Buttons::default()
+            }
+        }
+    }
+
+    /// Tracks the pressed components, and returns whether they're still within `window` of the first press.
+    ///
+    /// Returns `None` once all components are released, replaying any held back ones as a tap.
+    pub(super) fn press(&mut self, pressed: Buttons, window: Option<Duration>, now: Instant) -> Option<bool> {
+        if pressed == Buttons::default() {
+            let tap = match self.held_back == Buttons::default() {
+                true => self.tap,
+                false => Some((self.held_back, now)),
+            };
+
+            *self = HoldBack {
+                tap,
+                ..Default::default()
+            };
+            return None;
+        }
+
+        let first = *self.first.get_or_insert(now);
+        Some(window.is_none_or(|window| now - first <= window))
+    }
+
+    pub(super) fn consumed(&self) -> bool {
+        self.consumed
+    }
+
+    /// Marks the combo as completed, until all of its components are released.
+    pub(super) fn consume(&mut self) {
+        self.consumed = true;
+    }
+
+    /// Holds back the pressed components while the combo might be starting.
+    ///
+    /// Returns true if `suppress` is set and the combo's components should be hidden.
+    pub(super) fn hold(&mut self, pressed: Buttons, in_window: bool, window: Option<Duration>, suppress: bool) -> bool {
+        let pending = !self.consumed && in_window && window.is_some();
+
+        self.held_back = match suppress && pending {
+            true => Buttons(self.held_back.0 | pressed.0),
+            false => Buttons::default(),
+        };
+
+        suppress && (self.consumed || pending)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ChordState {
+    active: bool,
+    hold: HoldBack,
+}
+
+/// Applies all chords to the held source buttons.
+///
+/// Returns the source buttons left after suppression, and the output buttons pressed by chords.
+/// The other actions of active chords are added to `actions`.
+pub(super) fn apply(
+    chords: &[Chord],
+    states: &mut [ChordState],
+    held: Buttons,
+    now: Instant,
+    actions: &mut Vec<ButtonAction>,
+) -> (Buttons, Buttons) {
+    let mut suppressed = Buttons::default();
+    let mut tapped = Buttons::default();
+    let mut output = Buttons::default();
+
+    for (chord, state) in chords.iter().zip(states) {
+        let components = chord.components();
+
+        if components == Buttons::default() {
+            continue;
+        }
+
+        tapped = tapped | state.hold.tapped(now);
+
+        let pressed = Buttons(held.0 & components.0);
+
+        let Some(in_window) = state.hold.press(pressed, chord.window(), now)
+        else {
+            state.active = false;
+            continue;
+        };
+
+        if pressed == components {
+            if !state.active && !state.hold.consumed() && in_window {
+                state.active = true;
+                state.hold.consume();
+            }
+        } else {
+            state.active = false;
+        }
+
+        if state.active {
+            output = output | chord.output.buttons;
+            actions.extend_from_slice(&chord.output.actions);
+        }
+
+        if state.hold.hold(pressed, in_window, chord.window(), chord.suppress) {
+            suppressed = suppressed | components;
+        }
+    }
+
+    (Buttons((held.0 & !suppressed.0) | tapped.0), output)
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// Maps an input between 0.0 and 1.0 to an output between 0.0 and 1.0
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Curve {
+    #[default]
+    Linear,
+    /// `value ^ exponent`
+    Power(f32),
+    /// Exponential growth with the given steepness, negative values curve the other way.
+    Exponential(f32),
+    /// Straight lines between `[input, output]` points, which start at `[0, 0]` and end at `[1, 1]`.
+    Points(Vec<[f32; 2]>),
+}
+
+impl Curve {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Curve::Linear => "Linear",
+            Curve::Power(_) => "Power",
+            Curve::Exponential(_) => "Exponential",
+            Curve::Points(_) => "Custom",
+        }
+    }
+
+    pub fn is_linear(&self) -> bool {
+        match self {
+            Curve::Linear => true,
+            Curve::Power(exponent) => *exponent == 1.0,
+            Curve::Exponential(steepness) => *steepness == 0.0,
+            Curve::Points(points) => points.is_empty(),
+        }
+    }
+
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(0.0, 1.0);
+
+        let output = match self {
+            Curve::Linear => value,
+            Curve::Power(exponent) => {
+                if *exponent <= 0.0 {
+                    return value;
+                }
+
+                value.powf(*exponent)
+            }
+            Curve::Exponential(steepness) => {
+                if steepness.abs() <= f32::EPSILON {
+                    return value;
+                }
+
+                (f32::exp(steepness * value) - 1.0) / (f32::exp(*steepness) - 1.0)
+            }
+            Curve::Points(points) => Self::interpolate(points, value),
+        };
+
+        output.clamp(0.0, 1.0)
+    }
+
+    fn interpolate(points: &[[f32; 2]], value: f32) -> f32 {
+        let mut prev = [0.0, 0.0];
+
+        for &point in points.iter().chain(std::iter::once(&[1.0, 1.0])) {
+            if value <= point[0] {
+                let width = point[0] - prev[0];
+
+                if width <= f32::EPSILON {
+                    return point[1];
+                }
+
+                let influence = (value - prev[0]) / width;
+                return prev[1] + (point[1] - prev[1]) * influence;
+            }
+
+            prev = point;
+        }
+
+        prev[1]
+    }
+
+    /// Sorts points by input so they can be interpolated.
+    pub fn sort(&mut self) {
+        if let Curve::Points(points) = self {
+            for point in points.iter_mut() {
+                point[0] = point[0].clamp(0.0, 1.0);
+                point[1] = point[1].clamp(0.0, 1.0);
+            }
+
+            points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        }
+    }
+}
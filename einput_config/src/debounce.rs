@@ -0,0 +1,129 @@
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use einput_device::input::buttons::Button;
+use serde::{Deserialize, Serialize};
+
+/// Ignores changes to a button that don't last long enough.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Debounce {
+    /// Milliseconds a press has to last before it's passed on.
+    pub press: u32,
+    /// Milliseconds a release has to last before it's passed on.
+    pub release: u32,
+    /// Counts ignored changes in [`ChatterStats`].
+    pub count_chatter: bool,
+}
+
+impl Default for Debounce {
+    fn default() -> Self {
+        Self {
+            press: 0,
+            release: 10,
+            count_chatter: true,
+        }
+    }
+}
+
+impl Debounce {
+    pub(super) fn needs_tick(&self) -> bool {
+        self.press > 0 || self.release > 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct DebounceState {
+    pressed: bool,
+    /// When the button started being different from `pressed`.
+    changed: Option<Instant>,
+}
+
+impl DebounceState {
+    pub(super) fn update(
+        &mut self,
+        debounce: &Debounce,
+        button: Button,
+        stats: &ChatterStats,
+        held: bool,
+        now: Instant,
+    ) -> bool {
+        if held == self.pressed {
+            if self.changed.take().is_some() && debounce.count_chatter {
+                stats.add_chatter(button);
+            }
+
+            return self.pressed;
+        }
+
+        let changed = *self.changed.get_or_insert(now);
+
+        let time = match held {
+            true => debounce.press,
+            false => debounce.release,
+        };
+
+        if now - changed >= Duration::from_millis(time as u64) {
+            self.pressed = held;
+            self.changed = None;
+
+            if held && debounce.count_chatter {
+                stats.add_press(button);
+            }
+        }
+
+        self.pressed
+    }
+}
+
+/// Presses and ignored changes for each button, shared by every copy of a config.
+#[derive(Clone, Default)]
+pub struct ChatterStats(Arc<[ButtonChatter; Button::ALL.len()]>);
+
+#[derive(Default)]
+struct ButtonChatter {
+    presses: AtomicU32,
+    chatter: AtomicU32,
+}
+
+impl ChatterStats {
+    /// Fraction of ignored changes to presses after which a switch is considered failing.
+    const FAILING_RATIO: f32 = 0.1;
+    const FAILING_MIN: u32 = 5;
+
+    /// Returns the number of presses and ignored changes for a button.
+    pub fn get(&self, button: Button) -> (u32, u32) {
+        let chatter = &self.0[button as usize];
+
+        (
+            chatter.presses.load(Ordering::Relaxed),
+            chatter.chatter.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Returns true if a button chatters often enough that its switch is probably failing.
+    pub fn failing(&self, button: Button) -> bool {
+        let (presses, chatter) = self.get(button);
+
+        chatter >= Self::FAILING_MIN && chatter as f32 >= presses as f32 * Self::FAILING_RATIO
+    }
+
+    pub fn reset(&self) {
+        for chatter in self.0.iter() {
+            chatter.presses.store(0, Ordering::Relaxed);
+            chatter.chatter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn add_press(&self, button: Button) {
+        self.0[button as usize].presses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_chatter(&self, button: Button) {
+        self.0[button as usize].chatter.fetch_add(1, Ordering::Relaxed);
+    }
+}
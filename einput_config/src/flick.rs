@@ -0,0 +1,170 @@
+use std::{
+    f32::consts::PI,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use einput_device::input::{DeviceInput, sticks::StickId};
+use serde::{Deserialize, Serialize};
+
+/// Turns the camera by the angle a stick is flicked to, and by how far it's rotated after.
+///
+/// The turn is sent as horizontal deflection of the output stick over time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlickStick {
+    pub input: StickId,
+    pub output: StickId,
+    /// Distance from the center at which a flick starts.
+    pub threshold: f32,
+    /// Seconds a flick takes, if the game can turn fast enough.
+    pub flick_time: f32,
+    /// Degrees/second the game turns at with the output stick fully pushed.
+    pub turn_rate: f32,
+    /// Set from the configure window to turn a full circle with the current settings.
+    #[serde(skip)]
+    pub test: FlickTest,
+}
+
+impl Default for FlickStick {
+    fn default() -> Self {
+        Self {
+            input: StickId::Right,
+            output: StickId::Right,
+            threshold: 0.9,
+            flick_time: 0.1,
+            turn_rate: 360.0,
+            test: FlickTest::default(),
+        }
+    }
+}
+
+/// Starts a test turn in every copy of a config.
+#[derive(Clone, Debug, Default)]
+pub struct FlickTest(Arc<AtomicBool>);
+
+impl FlickTest {
+    /// Turns a full circle.
+    pub fn start(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Seconds rotating the stick takes to catch up, so the output doesn't depend on how often inputs arrive.
+const CATCH_UP: f32 = 0.016;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct FlickState {
+    /// Stick angle in degrees while past the threshold.
+    angle: Option<f32>,
+    /// Degrees left to turn for the current flick, and degrees/second to turn them at.
+    flick: Option<(f32, f32)>,
+    /// Degrees left to turn from rotating the stick.
+    rotation: f32,
+    /// Deflection output for the flick and for rotation since the last input.
+    output: [f32; 2],
+    last: Option<Instant>,
+}
+
+impl FlickState {
+    /// Turns the camera with the output stick's X axis, adding to what's mapped there.
+    ///
+    /// The input stick is always centered.
+    pub(super) fn apply(&mut self, flick: &FlickStick, input: &mut DeviceInput, now: Instant) {
+        let Some(stick) = input.get_mut(flick.input)
+        else { return };
+
+        let stick = std::mem::take(stick);
+
+        let dt = self.last.map_or(0.0, |last| (now - last).as_secs_f32());
+        self.last = Some(now);
+
+        self.charge(flick, dt);
+
+        if flick.test.take() {
+            self.start_flick(flick, 360.0);
+        }
+
+        if stick.length() >= flick.threshold {
+            // Up is 0 degrees and turning right is positive, with y positive downwards
+            let angle = stick.x.atan2(-stick.y) * 180.0 / PI;
+
+            match self.angle {
+                Some(last) => self.rotation += wrap(angle - last),
+                None => self.start_flick(flick, angle),
+            }
+
+            self.angle = Some(angle);
+        } else {
+            self.angle = None;
+        }
+
+        let turn = self.turn(flick);
+
+        let Some(output) = input.get_mut(flick.output)
+        else { return };
+
+        output.x = (output.x + turn).clamp(-1.0, 1.0);
+    }
+
+    fn start_flick(&mut self, flick: &FlickStick, angle: f32) {
+        let time = flick.flick_time.max(angle.abs() / flick.turn_rate);
+
+        // Anything left of the last flick is turned as rotation
+        if let Some((remaining, _)) = self.flick.take() {
+            self.rotation += remaining;
+        }
+
+        self.flick = (time > 0.0).then(|| (angle, angle.abs() / time));
+    }
+
+    /// Takes off what the game turned over `dt` from the last output.
+    fn charge(&mut self, flick: &FlickStick, dt: f32) {
+        let [flick_output, rotation_output] = self.output.map(|output| output * flick.turn_rate * dt);
+
+        self.rotation -= rotation_output;
+
+        if let Some((remaining, _)) = &mut self.flick {
+            let left = *remaining - flick_output;
+
+            // Overshooting the flick is made up for by rotation
+            if left == 0.0 || left.signum() != remaining.signum() {
+                self.rotation += left;
+                self.flick = None;
+            } else {
+                *remaining = left;
+            }
+        }
+    }
+
+    /// Returns the output stick deflection to turn with until the next input.
+    fn turn(&mut self, flick: &FlickStick) -> f32 {
+        if flick.turn_rate <= 0.0 {
+            self.output = [0.0; 2];
+            return 0.0;
+        }
+
+        let flick_output = self
+            .flick
+            .map_or(0.0, |(remaining, rate)| remaining.signum() * (rate / flick.turn_rate).min(1.0));
+
+        let room = 1.0 - flick_output.abs();
+        let rotation_output = (self.rotation / (flick.turn_rate * CATCH_UP)).clamp(-room, room);
+
+        self.output = [flick_output, rotation_output];
+
+        flick_output + rotation_output
+    }
+}
+
+/// Wraps degrees into -180..=180.
+fn wrap(angle: f32) -> f32 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
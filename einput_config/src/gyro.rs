@@ -0,0 +1,170 @@
+use std::time::Instant;
+
+use einput_device::input::{
+    DeviceInput,
+    buttons::{Button, Buttons},
+    sticks::StickId,
+};
+use einput_util::axis::Stick;
+use serde::{Deserialize, Serialize};
+
+/// Moves a stick by turning the device.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GyroStick {
+    pub stick: StickId,
+    pub mode: GyroMode,
+    /// Multiplies the turning speed. At 1.0, turning [`GyroStick::FULL_SPEED`] fully pushes the stick.
+    pub sensitivity: f32,
+    /// Inverts the X and Y axes.
+    pub invert: [bool; 2],
+    /// Degrees/second below which turning is ignored.
+    pub deadzone: f32,
+    /// Seconds the output takes to follow the device, or 0.0 for no smoothing.
+    pub smoothing: f32,
+    /// Source button that enables or disables the gyro.
+    pub button: Option<Button>,
+    pub button_mode: GyroButtonMode,
+}
+
+impl Default for GyroStick {
+    fn default() -> Self {
+        Self {
+            stick: StickId::Right,
+            mode: GyroMode::Blend,
+            sensitivity: 1.0,
+            invert: [false; 2],
+            deadzone: 2.0,
+            smoothing: 0.0,
+            button: None,
+            button_mode: GyroButtonMode::Ratchet,
+        }
+    }
+}
+
+impl GyroStick {
+    /// Degrees/second that fully pushes the stick at a sensitivity of 1.0.
+    pub const FULL_SPEED: f32 = 360.0;
+
+    /// Returns the stick position for the device's turning speed, before smoothing.
+    fn target(&self, input: &DeviceInput) -> Option<[f32; 2]> {
+        let gyro = input.gyroscope()?;
+
+        // Turning up moves the stick up, which is negative
+        let mut target = [gyro.yaw, -gyro.pitch];
+
+        for (value, invert) in target.iter_mut().zip(self.invert) {
+            let speed = value.abs();
+
+            *value = match speed > self.deadzone {
+                true => value.signum() * (speed - self.deadzone),
+                false => 0.0,
+            };
+
+            *value *= self.sensitivity / Self::FULL_SPEED;
+
+            if invert {
+                *value = -*value;
+            }
+        }
+
+        Some(target)
+    }
+
+    fn enabled(&self, held: Buttons) -> bool {
+        let Some(button) = self.button
+        else { return true };
+
+        match self.button_mode {
+            GyroButtonMode::Enable => held.is_pressed(button),
+            GyroButtonMode::Ratchet => !held.is_pressed(button),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GyroMode {
+    /// Adds to the physical stick.
+    #[default]
+    Blend,
+    /// Ignores the physical stick.
+    Replace,
+}
+
+impl GyroMode {
+    pub const ALL: [Self; 2] = [Self::Blend, Self::Replace];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GyroMode::Blend => "Blend",
+            GyroMode::Replace => "Replace",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GyroButtonMode {
+    /// The gyro only works while the button is held.
+    Enable,
+    /// The gyro stops while the button is held, to reposition the device.
+    #[default]
+    Ratchet,
+}
+
+impl GyroButtonMode {
+    pub const ALL: [Self; 2] = [Self::Enable, Self::Ratchet];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GyroButtonMode::Enable => "Enable",
+            GyroButtonMode::Ratchet => "Ratchet",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct GyroState {
+    smoothed: [f32; 2],
+    last: Option<Instant>,
+}
+
+impl GyroState {
+    /// Moves the configured stick in `input`, using the source buttons in `held`.
+    pub(super) fn apply(&mut self, gyro: &GyroStick, input: &mut DeviceInput, held: Buttons, now: Instant) {
+        let target = match gyro.enabled(held) {
+            true => gyro.target(input),
+            false => Some([0.0; 2]),
+        };
+
+        let Some(target) = target
+        else { return };
+
+        let dt = self.last.map_or(0.0, |last| (now - last).as_secs_f32());
+        self.last = Some(now);
+
+        let amount = match gyro.smoothing > 0.0 {
+            true => 1.0 - (-dt / gyro.smoothing).exp(),
+            false => 1.0,
+        };
+
+        for (smoothed, target) in self.smoothed.iter_mut().zip(target) {
+            *smoothed += (target - *smoothed) * amount;
+        }
+
+        let Some(stick) = input.get_mut(gyro.stick)
+        else { return };
+
+        let [x, y] = self.smoothed;
+
+        *stick = match gyro.mode {
+            GyroMode::Blend => Stick {
+                x: (stick.x + x).clamp(-1.0, 1.0),
+                y: (stick.y + y).clamp(-1.0, 1.0),
+            },
+            GyroMode::Replace => Stick {
+                x: x.clamp(-1.0, 1.0),
+                y: y.clamp(-1.0, 1.0),
+            },
+        };
+    }
+}
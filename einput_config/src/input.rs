@@ -1,63 +1,420 @@
-use std::f32::consts::PI;
+use std::{
+    f32::consts::PI,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 use einput_device::input::{
     DeviceInput,
     buttons::{Button, Buttons},
+    sticks::StickId,
     triggers::TriggerId,
 };
-use einput_util::axis::{Stick, Trigger};
+use einput_util::axis::{Stick, Trigger, TriggerAxis};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    chord::{self, Chord, ChordState},
+    curve::Curve,
+    debounce::{ChatterStats, Debounce, DebounceState},
+    mapping::{self, Direction, Mapping, MappingState},
+    socd::Socd,
+    turbo::{Turbo, TurboState},
+};
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DeviceInputConfig {
-    pub buttons: [Button; Button::ALL.len()],
+    pub buttons: [ButtonTargets; Button::ALL.len()],
+    /// Turbo for each source button
+    pub turbo: [Option<Turbo>; Button::ALL.len()],
+    /// Source buttons that stay pressed after a tap, until the next tap.
+    pub latch: [bool; Button::ALL.len()],
+    /// Shared with the compiled config, to show engaged latches in the configure window
+    #[serde(skip)]
+    pub latched: LatchedOutputs,
+    /// Debounce for each source button
+    pub debounce: [Option<Debounce>; Button::ALL.len()],
+    /// Shared with the compiled config, to show chatter in the configure window
+    #[serde(skip)]
+    pub chatter: ChatterStats,
+    pub chords: Vec<Chord>,
+    /// Resolves opposing D-pad directions in the output.
+    pub socd: Socd,
     pub sticks: [StickConfig; 2],
+    /// Swaps the left and right sticks after each has been configured.
+    pub swap_sticks: bool,
     pub triggers: [TriggerConfig; TriggerId::ALL.len()],
+    pub mappings: Vec<Mapping>,
 }
 
 impl Default for DeviceInputConfig {
     fn default() -> Self {
         Self {
-            buttons: Button::ALL,
+            buttons: Button::ALL.map(ButtonTargets::from),
+            turbo: [None; Button::ALL.len()],
+            latch: [false; Button::ALL.len()],
+            latched: LatchedOutputs::default(),
+            debounce: [None; Button::ALL.len()],
+            chatter: ChatterStats::default(),
+            chords: Vec::new(),
+            socd: Socd::default(),
             sticks: Default::default(),
+            swap_sticks: false,
             triggers: TriggerId::ALL.map(|id| TriggerConfig {
                 remap_to: id,
                 min: 0,
                 max: 255,
+                curve: Curve::Linear,
+                latch: false,
             }),
+            mappings: Vec::new(),
         }
     }
 }
 
+/// State kept between inputs by a compiled [`DeviceInputConfig`].
+pub(super) struct InputState {
+    mappings: Vec<MappingState>,
+    turbo: [TurboState; Button::ALL.len()],
+    latches: [Latch; Button::ALL.len()],
+    debounce: [DebounceState; Button::ALL.len()],
+    chords: Vec<ChordState>,
+    trigger_latches: [Latch; TriggerId::ALL.len()],
+    /// Actions of the pressed targets, kept to reuse the allocation.
+    actions: Vec<ButtonAction>,
+}
+
+/// Outputs held by engaged latches, shared by every copy of a config.
+#[derive(Clone, Default)]
+pub struct LatchedOutputs(Arc<LatchedBits>);
+
+#[derive(Default)]
+struct LatchedBits {
+    buttons: AtomicU32,
+    triggers: AtomicU32,
+}
+
+impl LatchedOutputs {
+    /// Output buttons pressed by an engaged latching source button.
+    pub fn buttons(&self) -> Buttons {
+        Buttons(self.0.buttons.load(Ordering::Relaxed))
+    }
+
+    /// Returns true if an output trigger is held by an engaged latching trigger.
+    pub fn trigger(&self, id: TriggerId) -> bool {
+        self.0.triggers.load(Ordering::Relaxed) & (1 << id as u32) != 0
+    }
+
+    fn set_buttons(&self, buttons: Buttons) {
+        self.0.buttons.store(buttons.0, Ordering::Relaxed);
+    }
+
+    fn set_triggers(&self, triggers: u32) {
+        self.0.triggers.store(triggers, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Latch {
+    held: bool,
+    latched: bool,
+}
+
+impl Latch {
+    /// Toggles on each new press and returns whether it's latched.
+    fn update(&mut self, held: bool) -> bool {
+        if held && !self.held {
+            self.latched = !self.latched;
+        }
+
+        self.held = held;
+        self.latched
+    }
+}
+
 impl DeviceInputConfig {
-    pub(super) fn apply(&self, device: &mut DeviceInput) {
+    pub(super) fn state(&self) -> InputState {
+        InputState {
+            mappings: vec![MappingState::default(); self.mappings.len()],
+            turbo: Default::default(),
+            latches: Default::default(),
+            debounce: Default::default(),
+            chords: vec![ChordState::default(); self.chords.len()],
+            trigger_latches: Default::default(),
+            actions: Vec::new(),
+        }
+    }
+
+    /// Returns true if the output can change without new input.
+    pub(super) fn needs_tick(&self) -> bool {
+        self.turbo.iter().any(Option::is_some)
+            || self.chords.iter().any(Chord::needs_tick)
+            || self.debounce.iter().flatten().any(Debounce::needs_tick)
+    }
+
+    /// Sets `macros[i]` for each macro started by a pressed target, leaving the rest alone.
+    pub(super) fn apply(&self, device: &mut DeviceInput, state: &mut InputState, macros: &mut [bool]) {
+        let raw = (!self.mappings.is_empty()).then(|| device.clone());
+        let mut latched_triggers = 0;
+
+        state.actions.clear();
+
         if let Some(buttons) = device.buttons_mut() {
             let mut new_buttons = Buttons::default();
 
-            for button in buttons.get_pressed() {
-                let button = self.buttons[button as usize];
-                new_buttons.set(button, true);
+            let now = Instant::now();
+
+            let mut held = *buttons;
+
+            for button in Button::ALL {
+                if let Some(debounce) = &self.debounce[button as usize] {
+                    let pressed = state.debounce[button as usize].update(
+                        debounce,
+                        button,
+                        &self.chatter,
+                        held.is_pressed(button),
+                        now,
+                    );
+                    held.set(button, pressed);
+                }
+            }
+
+            let (held, chord_buttons) = chord::apply(&self.chords, &mut state.chords, held, now, &mut state.actions);
+            let mut latched = Buttons::default();
+
+            for button in Button::ALL {
+                let targets = &self.buttons[button as usize];
+                let mut pressed = held.is_pressed(button);
+
+                if self.latch[button as usize] {
+                    pressed = state.latches[button as usize].update(pressed);
+
+                    if pressed {
+                        latched = latched | targets.buttons;
+                        latched_triggers |= targets.triggers();
+                    }
+                }
+
+                if let Some(turbo) = &self.turbo[button as usize] {
+                    pressed = state.turbo[button as usize].update(turbo, pressed, now);
+                }
+
+                if pressed {
+                    new_buttons = new_buttons | targets.buttons;
+                    state.actions.extend_from_slice(&targets.actions);
+                }
             }
 
-            *buttons = new_buttons;
+            *buttons = new_buttons | chord_buttons;
+            self.latched.set_buttons(latched);
         }
 
         if let Some(sticks) = device.sticks_mut() {
             sticks.left = self.sticks[0].apply(sticks.left);
             sticks.right = self.sticks[1].apply(sticks.right);
+
+            if self.swap_sticks {
+                std::mem::swap(&mut sticks.left, &mut sticks.right);
+            }
         }
 
         if let Some(triggers) = device.triggers_mut() {
             let mut new_triggers = triggers.clone();
+            let mut latched = 0;
 
             for id in TriggerId::ALL {
-                let new_id = self.triggers[id as usize].remap_to;
+                let config = &self.triggers[id as usize];
+                let mut value = config.apply(*triggers.get(id));
+
+                if config.latch {
+                    let held = value.0.to_f32() >= TriggerConfig::LATCH_THRESHOLD;
+
+                    if state.trigger_latches[id as usize].update(held) {
+                        value = Trigger::max();
+                        latched |= 1 << config.remap_to as u32;
+                    }
+                }
 
-                *new_triggers.get_mut(new_id) = self.triggers[id as usize].apply(*triggers.get(id));
+                *new_triggers.get_mut(config.remap_to) = value;
             }
 
             *triggers = new_triggers;
+            self.latched.set_triggers(latched | latched_triggers);
+        }
+
+        if let Some(raw) = raw {
+            mapping::apply(&self.mappings, &mut state.mappings, &raw, device);
+        }
+
+        apply_actions(&state.actions, device, macros);
+    }
+}
+
+/// Presses each action in `input`, and sets `macros[i]` for each macro action.
+pub(super) fn apply_actions(actions: &[ButtonAction], input: &mut DeviceInput, macros: &mut [bool]) {
+    let mut sticks = [Stick::default(); 2];
+
+    for &action in actions {
+        match action {
+            ButtonAction::Trigger(id) => {
+                if let Some(trigger) = input.get_mut(id) {
+                    *trigger = Trigger::max();
+                }
+            }
+            ButtonAction::Stick { id, direction } => {
+                let vector = direction.vector();
+                let stick = &mut sticks[id as usize];
+                stick.x += vector.x;
+                stick.y += vector.y;
+            }
+            ButtonAction::Macro(i) => {
+                if let Some(pressed) = macros.get_mut(i) {
+                    *pressed = true;
+                }
+            }
+        }
+    }
+
+    for id in StickId::ALL {
+        let pushed = sticks[id as usize];
+
+        if pushed.length() <= f32::EPSILON {
+            continue;
+        }
+
+        if let Some(stick) = input.get_mut(id) {
+            stick.x += pushed.x;
+            stick.y += pushed.y;
+
+            if stick.length() > 1.0 {
+                *stick = stick.normalized();
+            }
+        }
+    }
+}
+
+/// Buttons and other actions pressed by a source button, which can be none.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ButtonTargetsRepr", into = "ButtonTargetsRepr")]
+pub struct ButtonTargets {
+    pub buttons: Buttons,
+    pub actions: Vec<ButtonAction>,
+}
+
+impl ButtonTargets {
+    pub fn name(&self) -> String {
+        let names: Vec<String> = self
+            .buttons
+            .get_pressed()
+            .map(|button| button.name().to_owned())
+            .chain(self.actions.iter().map(ButtonAction::name))
+            .collect();
+
+        match names.is_empty() {
+            true => "None".to_owned(),
+            false => names.join(", "),
+        }
+    }
+
+    /// Returns true if `action` is one of the targets.
+    pub fn has_action(&self, action: ButtonAction) -> bool {
+        self.actions.contains(&action)
+    }
+
+    /// Adds or removes `action`.
+    pub fn set_action(&mut self, action: ButtonAction, pressed: bool) {
+        self.actions.retain(|&a| a != action);
+
+        if pressed {
+            self.actions.push(action);
+        }
+    }
+
+    /// Returns a bit for each trigger pulled by an action.
+    fn triggers(&self) -> u32 {
+        self.actions.iter().fold(0, |triggers, action| match action {
+            ButtonAction::Trigger(id) => triggers | 1 << *id as u32,
+            _ => triggers,
+        })
+    }
+}
+
+impl From<Button> for ButtonTargets {
+    fn from(button: Button) -> Self {
+        (Buttons::default() | button).into()
+    }
+}
+
+impl From<Buttons> for ButtonTargets {
+    fn from(buttons: Buttons) -> Self {
+        ButtonTargets {
+            buttons,
+            actions: Vec::new(),
+        }
+    }
+}
+
+/// A target other than a button, fully pressed while its source is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ButtonAction {
+    Trigger(TriggerId),
+    Stick { id: StickId, direction: Direction },
+    /// Starts the macro at this index in the device's macros, or cancels it if it's playing.
+    Macro(usize),
+}
+
+impl ButtonAction {
+    pub fn name(&self) -> String {
+        match self {
+            ButtonAction::Trigger(id) => format!("{id:?} Trigger"),
+            ButtonAction::Stick { id, direction } => format!("{id:?} Stick {direction:?}"),
+            ButtonAction::Macro(i) => format!("Macro {}", i + 1),
+        }
+    }
+}
+
+// Configs saved before buttons could have several targets store a single button,
+// and targets without actions are saved as a list of buttons.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ButtonTargetsRepr {
+    One(Button),
+    Many(Vec<Button>),
+    Actions {
+        buttons: Vec<Button>,
+        actions: Vec<ButtonAction>,
+    },
+}
+
+impl From<ButtonTargetsRepr> for ButtonTargets {
+    fn from(repr: ButtonTargetsRepr) -> Self {
+        let (buttons, actions) = match repr {
+            ButtonTargetsRepr::One(button) => (vec![button], Vec::new()),
+            ButtonTargetsRepr::Many(buttons) => (buttons, Vec::new()),
+            ButtonTargetsRepr::Actions { buttons, actions } => (buttons, actions),
+        };
+
+        ButtonTargets {
+            buttons: buttons.into_iter().fold(Buttons::default(), |buttons, button| buttons | button),
+            actions,
+        }
+    }
+}
+
+impl From<ButtonTargets> for ButtonTargetsRepr {
+    fn from(targets: ButtonTargets) -> Self {
+        let buttons = targets.buttons.get_pressed().collect();
+
+        match targets.actions.is_empty() {
+            true => ButtonTargetsRepr::Many(buttons),
+            false => ButtonTargetsRepr::Actions {
+                buttons,
+                actions: targets.actions,
+            },
         }
     }
 }
@@ -67,26 +424,101 @@ pub struct TriggerConfig {
     pub remap_to: TriggerId,
     pub min: u8,
     pub max: u8,
+    #[serde(default)]
+    pub curve: Curve,
+    /// Stays fully pulled after one pull, until the next pull.
+    #[serde(default)]
+    pub latch: bool,
 }
 
 impl TriggerConfig {
-    fn apply(&self, value: Trigger) -> Trigger {
-        use einput_util::axis::TriggerAxis;
+    /// How far a latching trigger has to be pulled to toggle
+    pub const LATCH_THRESHOLD: f32 = 0.5;
+
+    /// Sets `min` and `max`, keeping `min` below `max`.
+    pub fn set_range(&mut self, min: u8, max: u8) {
+        self.max = max.max(1);
+        self.min = min.min(self.max - 1);
+    }
 
+    fn apply(&self, value: Trigger) -> Trigger {
         let value = value.0.to_f32();
         let min = self.min.to_f32();
         let max = self.max.to_f32();
         let range = max - min;
 
-        let value = ((value - min).max(0.0) / range).min(1.0);
+        // An empty range acts as a button at `min`
+        let value = if range <= 0.0 {
+            if value > min { 1.0 } else { 0.0 }
+        } else {
+            ((value - min).max(0.0) / range).min(1.0)
+        };
+
+        u8::from_f32(self.curve.apply(value)).into()
+    }
+}
+
+/// Records the full travel of a trigger.
+pub struct TriggerSampler {
+    min: u8,
+    max: u8,
+}
 
-        u8::from_f32(value).into()
+impl Default for TriggerSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TriggerSampler {
+    pub fn new() -> Self {
+        Self {
+            min: u8::MAX,
+            max: u8::MIN,
+        }
+    }
+
+    pub fn add(&mut self, trigger: Trigger) {
+        self.min = self.min.min(trigger.0);
+        self.max = self.max.max(trigger.0);
+    }
+
+    /// Returns the recorded `(min, max)`, or `None` if the trigger hasn't moved.
+    pub fn range(&self) -> Option<(u8, u8)> {
+        (self.min < self.max).then_some((self.min, self.max))
     }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StickConfig {
+    /// Inner deadzone used by the radial modes.
     pub deadzone: f32,
+    /// Inner deadzone of the X and Y axes used by [`DeadzoneMode::Axial`].
+    pub axis_deadzones: [f32; 2],
+    /// Distance from the edge after which the stick is fully pushed.
+    pub outer_deadzone: f32,
+    /// Smallest output outside of the deadzone, to get past a game's own deadzone.
+    pub anti_deadzone: f32,
+    pub deadzone_mode: DeadzoneMode,
+    /// Response curve for the distance from the center.
+    pub curve: Curve,
+    /// Response curves for the X and Y axes, applied after `curve`.
+    pub axis_curves: [Curve; 2],
+    /// Inverts the X and Y axes.
+    pub invert: [bool; 2],
+    pub swap_axes: bool,
+    /// Rotation in degrees, clockwise.
+    pub rotation: f32,
+    /// Multiplies the X and Y axes.
+    pub scale: [f32; 2],
+    pub shape: ShapeMapping,
+    /// Resting position of the stick, which is subtracted from its input.
+    pub center: [f32; 2],
+    /// Measured angles of the 8 gate notches, starting from the right and going clockwise.
+    pub notches: Option<[f32; 8]>,
+    /// Angle in degrees around each notch that snaps to it.
+    pub notch_snap: f32,
     pub samples: Option<[f32; 32]>,
 }
 
@@ -94,36 +526,269 @@ impl Default for StickConfig {
     fn default() -> Self {
         Self {
             deadzone: 0.0,
+            axis_deadzones: [0.0; 2],
+            outer_deadzone: 0.0,
+            anti_deadzone: 0.0,
+            deadzone_mode: DeadzoneMode::ScaledRadial,
+            curve: Curve::Linear,
+            axis_curves: [Curve::Linear, Curve::Linear],
+            invert: [false; 2],
+            swap_axes: false,
+            rotation: 0.0,
+            scale: [1.0; 2],
+            shape: ShapeMapping::None,
+            center: [0.0; 2],
+            notches: None,
+            notch_snap: 0.0,
             samples: None,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadzoneMode {
+    /// Cuts off the stick's length inside the deadzone, without rescaling the rest.
+    Radial,
+    /// Cuts off the stick's length inside the deadzone and rescales the rest to start from 0.
+    #[default]
+    ScaledRadial,
+    /// Cuts off and rescales each axis separately.
+    Axial,
+}
+
+impl DeadzoneMode {
+    pub const ALL: [Self; 3] = [Self::Radial, Self::ScaledRadial, Self::Axial];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DeadzoneMode::Radial => "Radial",
+            DeadzoneMode::ScaledRadial => "Scaled Radial",
+            DeadzoneMode::Axial => "Axial",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShapeMapping {
+    #[default]
+    None,
+    /// Maps a square range of motion to a circle.
+    SquareToCircle,
+    /// Maps a circular range of motion to a square, so diagonals reach the corners.
+    CircleToSquare,
+}
+
+impl ShapeMapping {
+    pub const ALL: [Self; 3] = [Self::None, Self::SquareToCircle, Self::CircleToSquare];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShapeMapping::None => "None",
+            ShapeMapping::SquareToCircle => "Square to Circle",
+            ShapeMapping::CircleToSquare => "Circle to Square",
+        }
+    }
+
+    // Elliptical grid mapping and its inverse
+    fn apply(self, value: Stick) -> Stick {
+        let Stick { x, y } = value;
+
+        match self {
+            ShapeMapping::None => value,
+            ShapeMapping::SquareToCircle => Stick {
+                x: x * (1.0 - y * y / 2.0).max(0.0).sqrt(),
+                y: y * (1.0 - x * x / 2.0).max(0.0).sqrt(),
+            },
+            ShapeMapping::CircleToSquare => {
+                let sqrt8 = 8.0f32.sqrt();
+                let (x2, y2) = (x * x, y * y);
+
+                let x = 0.5 * (2.0 + x2 - y2 + sqrt8 * x).max(0.0).sqrt()
+                    - 0.5 * (2.0 + x2 - y2 - sqrt8 * x).max(0.0).sqrt();
+                let y = 0.5 * (2.0 - x2 + y2 + sqrt8 * y).max(0.0).sqrt()
+                    - 0.5 * (2.0 - x2 + y2 - sqrt8 * y).max(0.0).sqrt();
+
+                Stick {
+                    x: x.clamp(-1.0, 1.0),
+                    y: y.clamp(-1.0, 1.0),
+                }
+            }
+        }
+    }
+}
+
 impl StickConfig {
     fn apply(&self, value: Stick) -> Stick {
-        if self.deadzone == 0.0 && self.samples.is_none() {
+        let [center_x, center_y] = self.center;
+        let value = Stick {
+            x: value.x - center_x,
+            y: value.y - center_y,
+        };
+
+        // Notches and the range of motion are measured from the centered stick, before anything changes its angle
+        let range = self.range(value);
+        let value = self.apply_notches(value);
+        let value = self.apply_deadzone(value, range);
+        self.apply_geometry(value)
+    }
+
+    /// Returns the measured distance to the edge of the range of motion in the stick's direction.
+    fn range(&self, value: Stick) -> f32 {
+        let Some(samples) = &self.samples
+        else { return 1.0 };
+
+        Self::sample(samples, f32::atan2(value.y, value.x).rem_euclid(2.0 * PI))
+    }
+
+    /// Remaps the angle between each pair of measured notches so the notches land on multiples of 45 degrees.
+    fn apply_notches(&self, value: Stick) -> Stick {
+        let Some(notches) = &self.notches
+        else { return value };
+
+        if value.length() <= f32::EPSILON {
+            return value;
+        }
+
+        let notch_angle = PI / 4.0;
+        let angle = f32::atan2(value.y, value.x).rem_euclid(2.0 * PI);
+
+        let mut new_angle = angle;
+
+        for i in 0..8 {
+            let start = notches[i];
+            let span = (notches[(i + 1) % 8] - start).rem_euclid(2.0 * PI);
+            let offset = (angle - start).rem_euclid(2.0 * PI);
+
+            if offset < span {
+                new_angle = (i as f32 + offset / span) * notch_angle;
+                break;
+            }
+        }
+
+        let snap = self.notch_snap.to_radians();
+        let nearest = (new_angle / notch_angle).round() * notch_angle;
+
+        if (new_angle - nearest).abs() <= snap {
+            new_angle = nearest;
+        }
+
+        let (sin, cos) = new_angle.sin_cos();
+
+        Stick {
+            x: cos * value.length(),
+            y: sin * value.length(),
+        }
+    }
+
+    fn apply_geometry(&self, value: Stick) -> Stick {
+        let mut value = self.shape.apply(value);
+
+        if self.swap_axes {
+            value = Stick { x: value.y, y: value.x };
+        }
+
+        if self.rotation != 0.0 {
+            // Stick y is positive downwards, so a positive angle rotates clockwise
+            let (sin, cos) = self.rotation.to_radians().sin_cos();
+            value = Stick {
+                x: value.x * cos - value.y * sin,
+                y: value.x * sin + value.y * cos,
+            };
+        }
+
+        let [invert_x, invert_y] = self.invert;
+        let [scale_x, scale_y] = self.scale;
+
+        Stick {
+            x: (value.x * scale_x * if invert_x { -1.0 } else { 1.0 }).clamp(-1.0, 1.0),
+            y: (value.y * scale_y * if invert_y { -1.0 } else { 1.0 }).clamp(-1.0, 1.0),
+        }
+    }
+
+    fn apply_deadzone(&self, value: Stick, max: f32) -> Stick {
+        let no_deadzone = match self.deadzone_mode {
+            DeadzoneMode::Axial => self.axis_deadzones == [0.0; 2],
+            _ => self.deadzone == 0.0,
+        };
+
+        if no_deadzone
+            && self.outer_deadzone == 0.0
+            && self.anti_deadzone == 0.0
+            && self.curve.is_linear()
+            && self.axis_curves.iter().all(Curve::is_linear)
+            && self.samples.is_none()
+        {
             return value;
         }
 
-        let max = match &self.samples {
-            Some(samples) => {
-                let mut angle = f32::atan2(value.y, value.x);
-                if angle < 0.0 {
-                    angle = 2.0 * std::f32::consts::PI + angle;
+        let outer = max - self.outer_deadzone;
+
+        let value = match self.deadzone_mode {
+            DeadzoneMode::Radial | DeadzoneMode::ScaledRadial => {
+                let new_length = self.scale(value.length(), self.deadzone, outer);
+                value.normalized() * new_length
+            }
+            DeadzoneMode::Axial => {
+                let [x_deadzone, y_deadzone] = self.axis_deadzones;
+
+                Stick {
+                    x: self.scale(value.x.abs(), x_deadzone, outer).copysign(value.x),
+                    y: self.scale(value.y.abs(), y_deadzone, outer).copysign(value.y),
                 }
-                Self::sample(samples, angle)
             }
-            None => 1.0,
         };
 
-        let range = max - self.deadzone;
+        let value = self.apply_curves(value);
+        let value = self.apply_anti_deadzone(value);
+
+        if value.length() > 1.0 {
+            value.normalized()
+        } else {
+            value
+        }
+    }
+
+    fn apply_curves(&self, value: Stick) -> Stick {
+        let value = value.normalized() * self.curve.apply(value.length());
+        let [x_curve, y_curve] = &self.axis_curves;
+
+        Stick {
+            x: x_curve.apply(value.x.abs()).copysign(value.x),
+            y: y_curve.apply(value.y.abs()).copysign(value.y),
+        }
+    }
+
+    fn apply_anti_deadzone(&self, value: Stick) -> Stick {
+        let anti = |value: f32| {
+            if value <= 0.0 {
+                return 0.0;
+            }
+
+            self.anti_deadzone + (1.0 - self.anti_deadzone) * value
+        };
+
+        match self.deadzone_mode {
+            DeadzoneMode::Radial | DeadzoneMode::ScaledRadial => {
+                value.normalized() * anti(value.length())
+            }
+            DeadzoneMode::Axial => Stick {
+                x: anti(value.x.abs()).copysign(value.x),
+                y: anti(value.y.abs()).copysign(value.y),
+            },
+        }
+    }
 
-        if range <= 0.0 {
-            return Stick::default();
+    /// Maps a distance from the center between `inner` and `outer` to a value between 0.0 and 1.0
+    fn scale(&self, value: f32, inner: f32, outer: f32) -> f32 {
+        if value <= inner || value <= 0.0 || outer <= inner {
+            return 0.0;
         }
 
-        let new_length = ((value.length() - self.deadzone).max(0.0) / range).min(1.0);
-        value.normalized() * new_length
+        match self.deadzone_mode {
+            DeadzoneMode::Radial => value / outer,
+            _ => (value - inner) / (outer - inner),
+        }
+        .min(1.0)
     }
 
     fn sample(samples: &[f32; 32], angle: f32) -> f32 {
@@ -154,14 +819,65 @@ impl StickConfig {
 
 pub struct StickSampler {
     pub samples: [f32; 32],
+    center_sum: [f32; 2],
+    center_count: u32,
+    /// Furthest `[length, angle]` seen around each notch.
+    notches: [Option<[f32; 2]>; 8],
 }
 
 impl StickSampler {
+    /// Minimum length for a sample to count as pushed into a notch.
+    const NOTCH_MIN_LENGTH: f32 = 0.5;
+
     pub fn new() -> Self {
-        Self { samples: [0.0; 32] }
+        Self {
+            samples: [0.0; 32],
+            center_sum: [0.0; 2],
+            center_count: 0,
+            notches: [None; 8],
+        }
     }
 
+    /// Adds a sample of the stick at rest.
+    pub fn add_center(&mut self, stick: Stick) {
+        self.center_sum[0] += stick.x;
+        self.center_sum[1] += stick.y;
+        self.center_count += 1;
+    }
+
+    /// Returns the average resting position.
+    pub fn center(&self) -> [f32; 2] {
+        if self.center_count == 0 {
+            return [0.0; 2];
+        }
+
+        self.center_sum.map(|sum| sum / self.center_count as f32)
+    }
+
+    /// Returns the angles of all 8 notches, or `None` if any of them haven't been reached.
+    pub fn notches(&self) -> Option<[f32; 8]> {
+        let mut notches = [0.0; 8];
+
+        for (notch, sample) in notches.iter_mut().zip(self.notches) {
+            *notch = sample?[1];
+        }
+
+        Some(notches)
+    }
+
+    /// Returns the indices of notches that haven't been reached, starting from the right and going clockwise.
+    pub fn missing_notches(&self) -> impl Iterator<Item = usize> + '_ {
+        self.notches.iter().enumerate().filter(|(_, notch)| notch.is_none()).map(|(i, _)| i)
+    }
+
+    /// Adds a sample of the stick's range of motion, relative to the center.
     pub fn add(&mut self, stick: Stick) {
+        let [center_x, center_y] = self.center();
+        let stick = Stick {
+            x: stick.x - center_x,
+            y: stick.y - center_y,
+        };
+
         let mut angle = f32::atan2(stick.y, stick.x);
         if angle < 0.0 {
             angle = 2.0 * PI + angle;
@@ -188,5 +904,105 @@ impl StickSampler {
         if influence >= 0.5 {
             self.samples[i2] = f32::max(self.samples[i2], stick.length());
         }
+
+        if stick.length() >= Self::NOTCH_MIN_LENGTH {
+            let notch = (angle / (PI / 4.0)).round() as usize % 8;
+            let furthest = &mut self.notches[notch];
+
+            if furthest.is_none_or(|[length, _]| stick.length() > length) {
+                *furthest = Some([stick.length(), angle]);
+            }
+        }
+    }
+}
+
+/// Records a stick or trigger left untouched, to measure its drift and noise.
+#[derive(Default)]
+pub struct RestSampler {
+    count: u32,
+    sum: [f32; 2],
+    sum_squares: f32,
+    max: f32,
+}
+
+impl RestSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, stick: Stick) {
+        self.count += 1;
+        self.sum[0] += stick.x;
+        self.sum[1] += stick.y;
+        self.sum_squares += stick.x.powi(2) + stick.y.powi(2);
+        self.max = self.max.max(stick.length());
+    }
+
+    pub fn add_trigger(&mut self, trigger: Trigger) {
+        self.add(Stick {
+            x: trigger.0.to_f32(),
+            y: 0.0,
+        });
+    }
+
+    pub fn stats(&self) -> Option<RestStats> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let count = self.count as f32;
+        let mean = Stick {
+            x: self.sum[0] / count,
+            y: self.sum[1] / count,
+        };
+        let variance = self.sum_squares / count - mean.length().powi(2);
+
+        Some(RestStats {
+            drift: mean.length(),
+            noise: variance.max(0.0).sqrt(),
+            max: self.max,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RestStats {
+    /// Distance of the average resting position from the center.
+    pub drift: f32,
+    /// Standard deviation around the average resting position.
+    pub noise: f32,
+    /// Furthest distance from the center.
+    pub max: f32,
+}
+
+impl RestStats {
+    const MARGIN: f32 = 1.25;
+
+    /// Returns a deadzone that covers every recorded sample, with some room for noise.
+    pub fn suggested_deadzone(&self) -> f32 {
+        ((self.max + self.noise) * Self::MARGIN).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notch_snaps_with_axial_deadzone() {
+        let notches = [0.0, 50.0, 90.0, 135.0, 180.0, 225.0, 270.0, 315.0].map(f32::to_radians);
+        let config = StickConfig {
+            deadzone_mode: DeadzoneMode::Axial,
+            axis_deadzones: [0.2; 2],
+            notches: Some(notches),
+            ..Default::default()
+        };
+
+        // Pushed into the measured down-right notch at 50 degrees
+        let (sin, cos) = 50.0f32.to_radians().sin_cos();
+        let value = config.apply(Stick { x: cos * 0.9, y: sin * 0.9 });
+
+        assert!(value.length() > 0.5);
+        assert!((value.x - value.y).abs() < 1e-4, "{value:?} isn't on the diagonal");
     }
 }
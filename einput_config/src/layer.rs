@@ -0,0 +1,110 @@
+use einput_device::input::buttons::{Button, Buttons};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    input::{ButtonTargets, DeviceInputConfig},
+    mapping::Mapping,
+};
+
+/// A set of mappings used instead of the base config while its button is held or toggled on.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layer {
+    pub name: String,
+    /// Source button that activates the layer, which is never passed through.
+    pub button: Button,
+    pub mode: LayerMode,
+    /// Targets for each source button, or `None` to use the base config's.
+    pub buttons: [Option<ButtonTargets>; Button::ALL.len()],
+    /// Used along with the base config's mappings.
+    pub mappings: Vec<Mapping>,
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self {
+            name: "Layer".to_owned(),
+            button: Button::Select,
+            mode: LayerMode::Hold,
+            buttons: std::array::from_fn(|_| None),
+            mappings: Vec::new(),
+        }
+    }
+}
+
+impl Layer {
+    /// Returns the base config with this layer's entries on top.
+    pub(super) fn merge(&self, base: &DeviceInputConfig) -> DeviceInputConfig {
+        let mut config = base.clone();
+
+        for (targets, layer_targets) in config.buttons.iter_mut().zip(&self.buttons) {
+            if let Some(layer_targets) = layer_targets {
+                targets.clone_from(layer_targets);
+            }
+        }
+
+        config.mappings.extend(self.mappings.iter().cloned());
+
+        config
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayerMode {
+    /// Active while the button is held.
+    #[default]
+    Hold,
+    /// Each press turns the layer on or off.
+    Toggle,
+}
+
+impl LayerMode {
+    pub const ALL: [Self; 2] = [Self::Hold, Self::Toggle];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayerMode::Hold => "Hold",
+            LayerMode::Toggle => "Toggle",
+        }
+    }
+}
+
+/// Tracks which layer is active.
+#[derive(Clone, Default)]
+pub(super) struct LayerState {
+    held: Buttons,
+    toggled: Vec<bool>,
+}
+
+impl LayerState {
+    /// Returns the index of the active layer, or `None` for the base config.
+    ///
+    /// Later layers take priority when more than one is active.
+    pub(super) fn update(&mut self, layers: &[Layer], held: Buttons) -> Option<usize> {
+        self.toggled.resize(layers.len(), false);
+
+        let mut active = None;
+
+        for (i, layer) in layers.iter().enumerate() {
+            let pressed = held.is_pressed(layer.button);
+
+            let on = match layer.mode {
+                LayerMode::Hold => pressed,
+                LayerMode::Toggle => {
+                    if pressed && !self.held.is_pressed(layer.button) {
+                        self.toggled[i] = !self.toggled[i];
+                    }
+
+                    self.toggled[i]
+                }
+            };
+
+            if on {
+                active = Some(i);
+            }
+        }
+
+        self.held = held;
+        active
+    }
+}
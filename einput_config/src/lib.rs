@@ -1,24 +1,146 @@
+pub mod chord;
+pub mod curve;
+pub mod debounce;
+pub mod flick;
+pub mod gyro;
 pub mod input;
+pub mod layer;
+pub mod macros;
+pub mod mapping;
+pub mod socd;
+pub mod tilt;
+pub mod turbo;
+
+use std::time::Instant;
 
 use einput_core::device::DeviceTransformer;
 use serde::{Deserialize, Serialize};
 
-use self::input::DeviceInputConfig;
+use self::{
+    flick::{FlickState, FlickStick},
+    gyro::{GyroState, GyroStick},
+    input::{ButtonAction, ButtonTargets, DeviceInputConfig},
+    layer::{Layer, LayerState},
+    macros::{Macro, MacroState},
+    socd::SocdState,
+    tilt::{Tilt, TiltState},
+};
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DeviceConfig {
     pub input: DeviceInputConfig,
+    pub layers: Vec<Layer>,
+    pub macros: Vec<Macro>,
+    pub flick_stick: Option<FlickStick>,
+    pub gyro: Option<GyroStick>,
+    pub tilt: Option<Tilt>,
 }
 
 impl DeviceConfig {
     pub fn compile(&self) -> DeviceTransformer {
-        let this = self.clone();
+        let base = self.without_layer_buttons(self.input.clone());
+        let layer_configs: Vec<DeviceInputConfig> = self
+            .layers
+            .iter()
+            .map(|layer| self.without_layer_buttons(layer.merge(&base)))
+            .collect();
+
+        let tick = base.needs_tick()
+            || layer_configs.iter().any(DeviceInputConfig::needs_tick)
+            || !self.macros.is_empty()
+            || self.flick_stick.is_some();
+        let layers = self.layers.clone();
+        let macros = self.macros.clone();
+        let flick_stick = self.flick_stick.clone();
+        let gyro = self.gyro;
+        let tilt = self.tilt.clone();
 
         DeviceTransformer::new(move || {
-            let this = this.clone();
+            let base = base.clone();
+            let layer_configs = layer_configs.clone();
+            let layers = layers.clone();
+            let macros = macros.clone();
+
+            let mut base_state = base.state();
+            let mut layer_states: Vec<_> = layer_configs.iter().map(DeviceInputConfig::state).collect();
+            let mut layer_state = LayerState::default();
+            let mut macro_states = vec![MacroState::default(); macros.len()];
+            let mut macros_pressed = vec![false; macros.len()];
+            let flick_stick = flick_stick.clone();
+            let mut flick_state = FlickState::default();
+            let mut gyro_state = GyroState::default();
+            let tilt = tilt.clone();
+            let mut tilt_state = TiltState::default();
+            let mut socd_states = [SocdState::default(); 2];
+
+            Box::new(move |input| {
+                let now = Instant::now();
+                let source = input.buttons().copied().unwrap_or_default();
+
+                macros::update(&macros, &mut macro_states, input, now);
+
+                let held = input.buttons().copied().unwrap_or_default();
+
+                macros_pressed.fill(false);
+
+                match layer_state.update(&layers, held) {
+                    Some(i) => layer_configs[i].apply(input, &mut layer_states[i], &mut macros_pressed),
+                    None => base.apply(input, &mut base_state, &mut macros_pressed),
+                }
+
+                macros::play(&mut macro_states, &macros_pressed, now);
+                macros::apply(&macros, &mut macro_states, input, now);
+
+                if let Some(flick_stick) = &flick_stick {
+                    flick_state.apply(flick_stick, input, now);
+                }
 
-            Box::new(move |input| { this.input.apply(input); })
+                if let Some(gyro) = &gyro {
+                    gyro_state.apply(gyro, input, source, now);
+                }
+
+                if let Some(tilt) = &tilt {
+                    tilt_state.apply(tilt, input, source);
+                }
+
+                // Last, so opposing directions pressed by any of the above are resolved too
+                if let Some(buttons) = input.buttons_mut() {
+                    socd::apply(&base.socd, &mut socd_states, buttons);
+                }
+            })
         })
+        .with_tick(tick)
+    }
+
+    /// Removes a macro, along with actions that start it, and moves actions for later macros to their new index.
+    pub fn remove_macro(&mut self, index: usize) {
+        self.macros.remove(index);
+
+        let targets = self
+            .input
+            .buttons
+            .iter_mut()
+            .chain(self.input.chords.iter_mut().map(|chord| &mut chord.output))
+            .chain(self.layers.iter_mut().flat_map(|layer| layer.buttons.iter_mut().flatten()));
+
+        for targets in targets {
+            targets.actions.retain_mut(|action| match action {
+                ButtonAction::Macro(i) if *i == index => false,
+                ButtonAction::Macro(i) if *i > index => {
+                    *i -= 1;
+                    true
+                }
+                _ => true,
+            });
+        }
     }
-}
\ No newline at end of file
+
+    fn without_layer_buttons(&self, mut config: DeviceInputConfig) -> DeviceInputConfig {
+        for layer in &self.layers {
+            config.buttons[layer.button as usize] = ButtonTargets::default();
+        }
+
+        config
+    }
+}
@@ -0,0 +1,199 @@
+use std::time::{Duration, Instant};
+
+use einput_device::input::{
+    DeviceInput,
+    buttons::{Button, Buttons},
+    sticks::StickId,
+    triggers::TriggerId,
+};
+use einput_util::axis::{Stick, TriggerAxis};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chord::HoldBack,
+    input::{self, ButtonTargets},
+};
+
+/// A timed sequence of inputs, played when its source buttons are pressed together.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Macro {
+    pub name: String,
+    /// Source buttons that start the macro. Pressing them again while it plays cancels it.
+    pub buttons: Vec<Button>,
+    /// Hides the source buttons from the rest of the config while they're all held or the macro is playing,
+    /// and holds them back for `window` while it might be starting.
+    pub suppress: bool,
+    /// Milliseconds between the first and last source button press, or 0 to not hold buttons back.
+    pub window: u32,
+    pub steps: Vec<MacroStep>,
+}
+
+impl Default for Macro {
+    fn default() -> Self {
+        Self {
+            name: "Macro".to_owned(),
+            buttons: Vec::new(),
+            suppress: true,
+            window: 100,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl Macro {
+    pub fn components(&self) -> Buttons {
+        self.buttons.iter().fold(Buttons::default(), |buttons, &button| buttons | button)
+    }
+
+    fn window(&self) -> Option<Duration> {
+        (self.window > 0).then(|| Duration::from_millis(self.window as u64))
+    }
+
+    fn step(&self, elapsed: Duration) -> Option<&MacroStep> {
+        let mut end = Duration::ZERO;
+
+        for step in &self.steps {
+            end += Duration::from_millis(step.duration as u64);
+
+            if elapsed < end {
+                return Some(step);
+            }
+        }
+
+        None
+    }
+}
+
+/// Inputs held for `duration`, on top of the device's own input.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MacroStep {
+    /// Milliseconds
+    pub duration: u32,
+    pub buttons: ButtonTargets,
+    /// Stick positions, or `None` to leave a stick alone.
+    pub sticks: [Option<[f32; 2]>; 2],
+    /// Trigger values between 0.0 and 1.0, or `None` to leave a trigger alone.
+    pub triggers: [Option<f32>; TriggerId::ALL.len()],
+}
+
+impl Default for MacroStep {
+    fn default() -> Self {
+        Self {
+            duration: 50,
+            buttons: ButtonTargets::default(),
+            sticks: [None; 2],
+            triggers: [None; TriggerId::ALL.len()],
+        }
+    }
+}
+
+impl MacroStep {
+    fn apply(&self, input: &mut DeviceInput) {
+        if let Some(buttons) = input.buttons_mut() {
+            *buttons = *buttons | self.buttons.buttons;
+        }
+
+        // Macros can't start other macros
+        input::apply_actions(&self.buttons.actions, input, &mut []);
+
+        for id in StickId::ALL {
+            if let (Some([x, y]), Some(stick)) = (self.sticks[id as usize], input.get_mut(id)) {
+                *stick = Stick { x, y };
+            }
+        }
+
+        for id in TriggerId::ALL {
+            if let (Some(value), Some(trigger)) = (self.triggers[id as usize], input.get_mut(id)) {
+                trigger.0 = u8::from_f32(value);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct MacroState {
+    held: bool,
+    start: Option<Instant>,
+    /// Holds back source buttons while the macro might be starting.
+    hold: HoldBack,
+    /// Set while a target with this macro as an action is pressed.
+    played: bool,
+}
+
+/// Starts and cancels macros from the held source buttons, and hides suppressed source buttons from `input`.
+pub(super) fn update(macros: &[Macro], states: &mut [MacroState], input: &mut DeviceInput, now: Instant) {
+    let Some(buttons) = input.buttons_mut()
+    else { return };
+
+    let held = *buttons;
+    let mut suppressed = Buttons::default();
+    let mut tapped = Buttons::default();
+
+    for (m, state) in macros.iter().zip(states) {
+        let components = m.components();
+
+        if components == Buttons::default() {
+            continue;
+        }
+
+        tapped = tapped | state.hold.tapped(now);
+
+        let pressed_components = Buttons(held.0 & components.0);
+        let pressed = pressed_components == components;
+
+        if pressed && !state.held {
+            state.start = match state.start {
+                Some(_) => None,
+                None => Some(now),
+            };
+        }
+
+        state.held = pressed;
+
+        let Some(in_window) = state.hold.press(pressed_components, m.window(), now)
+        else { continue };
+
+        if pressed {
+            state.hold.consume();
+        }
+
+        let hide = state.hold.hold(pressed_components, in_window, m.window(), m.suppress);
+
+        if hide || (m.suppress && state.start.is_some()) {
+            suppressed = suppressed | components;
+        }
+    }
+
+    *buttons = Buttons((held.0 & !suppressed.0) | tapped.0);
+}
+
+/// Starts or cancels macros on each new press of a target with the macro as an action.
+///
+/// `pressed` holds whether each macro is pressed as an action.
+pub(super) fn play(states: &mut [MacroState], pressed: &[bool], now: Instant) {
+    for (state, &pressed) in states.iter_mut().zip(pressed) {
+        if pressed && !state.played {
+            state.start = match state.start {
+                Some(_) => None,
+                None => Some(now),
+            };
+        }
+
+        state.played = pressed;
+    }
+}
+
+/// Plays the current step of each running macro into `input`.
+pub(super) fn apply(macros: &[Macro], states: &mut [MacroState], input: &mut DeviceInput, now: Instant) {
+    for (m, state) in macros.iter().zip(states) {
+        let Some(start) = state.start
+        else { continue };
+
+        match m.step(now - start) {
+            Some(step) => step.apply(input),
+            None => state.start = None,
+        }
+    }
+}
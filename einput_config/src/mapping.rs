@@ -0,0 +1,320 @@
+use std::f32::consts::PI;
+
+use einput_device::input::{
+    DeviceInput,
+    buttons::Button,
+    sticks::StickId,
+    triggers::TriggerId,
+};
+use einput_util::axis::{Stick, TriggerAxis};
+use serde::{Deserialize, Serialize};
+
+/// Maps one input to another, possibly of a different type.
+///
+/// Sources are read from the device's raw input, and targets are added on top of the rest of the config.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Mapping {
+    pub from: Source,
+    pub to: Target,
+    /// How an analog source presses a button target.
+    #[serde(default)]
+    pub actuation: Actuation,
+}
+
+impl Mapping {
+    pub fn new(from: Source, to: Target) -> Self {
+        Self {
+            from,
+            to,
+            actuation: Actuation::Threshold,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Source {
+    Button(Button),
+    /// Pressed once the trigger passes `threshold` (0.0 - 1.0).
+    Trigger { id: TriggerId, threshold: f32 },
+    /// Pressed once the stick passes `threshold` (0.0 - 1.0) inside the direction's sector.
+    Stick {
+        id: StickId,
+        direction: Direction,
+        sectors: Sectors,
+        threshold: f32,
+    },
+}
+
+impl Source {
+    pub fn name(&self) -> String {
+        match self {
+            Source::Button(button) => format!("{button:?}"),
+            Source::Trigger { id, .. } => format!("{id:?} Trigger"),
+            Source::Stick { id, direction, .. } => format!("{id:?} Stick {direction:?}"),
+        }
+    }
+
+    pub fn is_analog(&self) -> bool {
+        !matches!(self, Source::Button(_))
+    }
+
+    pub fn threshold(&self) -> f32 {
+        match *self {
+            Source::Button(_) => 1.0,
+            Source::Trigger { threshold, .. } | Source::Stick { threshold, .. } => threshold,
+        }
+    }
+
+    /// Returns a value between 0.0 and 1.0, which is 0.0 unless the source is pressed.
+    pub fn value(&self, input: &DeviceInput) -> f32 {
+        let amount = self.amount(input);
+
+        if amount >= self.threshold() && amount > 0.0 {
+            amount
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns a value between 0.0 and 1.0, ignoring the threshold.
+    fn amount(&self, input: &DeviceInput) -> f32 {
+        match *self {
+            Source::Button(button) => match input.get(button) {
+                Some(true) => 1.0,
+                _ => 0.0,
+            },
+            Source::Trigger { id, .. } => {
+                input.get(id).map(|trigger| trigger.0.to_f32()).unwrap_or(0.0)
+            }
+            Source::Stick {
+                id,
+                direction,
+                sectors,
+                ..
+            } => {
+                let stick = input.get(id).copied().unwrap_or_default();
+                let length = stick.length().min(1.0);
+
+                if length <= f32::EPSILON {
+                    return 0.0;
+                }
+
+                let offset = angle_between(f32::atan2(stick.y, stick.x), direction.angle());
+
+                if offset <= sectors.half_width() {
+                    length
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Actuation {
+    /// Pressed while the source is past its threshold.
+    #[default]
+    Threshold,
+    /// Pressed once the source passes its threshold, and released once it falls `release` below it.
+    Hysteresis { release: f32 },
+    /// Pressed once the source passes its threshold. After that, released once it moves back by `release`,
+    /// and pressed again once it moves forward by `press`.
+    Rapid { press: f32, release: f32 },
+}
+
+impl Actuation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Actuation::Threshold => "Threshold",
+            Actuation::Hysteresis { .. } => "Hysteresis",
+            Actuation::Rapid { .. } => "Rapid Trigger",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MappingState {
+    pressed: bool,
+    /// Furthest point while pressed, or closest point while released, since the last change.
+    extreme: Option<f32>,
+}
+
+impl MappingState {
+    fn update(&mut self, actuation: Actuation, threshold: f32, amount: f32) -> bool {
+        let past_threshold = amount >= threshold && amount > 0.0;
+
+        match actuation {
+            Actuation::Threshold => self.pressed = past_threshold,
+            Actuation::Hysteresis { release } => {
+                self.pressed = match self.pressed {
+                    true => amount > (threshold - release).max(0.0),
+                    false => past_threshold,
+                };
+            }
+            Actuation::Rapid { press, release } => {
+                if !past_threshold {
+                    self.pressed = false;
+                    self.extreme = None;
+                    return false;
+                }
+
+                let Some(extreme) = &mut self.extreme
+                else {
+                    self.pressed = true;
+                    self.extreme = Some(amount);
+                    return true;
+                };
+
+                if self.pressed {
+                    *extreme = extreme.max(amount);
+
+                    if amount <= *extreme - release {
+                        self.pressed = false;
+                        *extreme = amount;
+                    }
+                } else {
+                    *extreme = extreme.min(amount);
+
+                    if amount >= *extreme + press {
+                        self.pressed = true;
+                        *extreme = amount;
+                    }
+                }
+            }
+        }
+
+        self.pressed
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Target {
+    Button(Button),
+    /// Pulled as far as the source is pressed, so digital sources give a full pull.
+    Trigger(TriggerId),
+    /// Pushed in the direction as far as the source is pressed.
+    Stick { id: StickId, direction: Direction },
+}
+
+impl Target {
+    pub fn name(&self) -> String {
+        match self {
+            Target::Button(button) => format!("{button:?}"),
+            Target::Trigger(id) => format!("{id:?} Trigger"),
+            Target::Stick { id, direction } => format!("{id:?} Stick {direction:?}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    // Stick y is positive downwards
+    pub(super) fn vector(self) -> Stick {
+        match self {
+            Direction::Up => Stick { x: 0.0, y: -1.0 },
+            Direction::Down => Stick { x: 0.0, y: 1.0 },
+            Direction::Left => Stick { x: -1.0, y: 0.0 },
+            Direction::Right => Stick { x: 1.0, y: 0.0 },
+        }
+    }
+
+    fn angle(self) -> f32 {
+        let vector = self.vector();
+        f32::atan2(vector.y, vector.x)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sectors {
+    /// Only the closest direction is pressed.
+    Four,
+    /// Diagonals press both neighbouring directions.
+    Eight,
+}
+
+impl Sectors {
+    pub const ALL: [Self; 2] = [Self::Four, Self::Eight];
+
+    fn half_width(self) -> f32 {
+        match self {
+            Sectors::Four => PI / 4.0,
+            Sectors::Eight => PI * 3.0 / 8.0,
+        }
+    }
+}
+
+/// Applies all mappings, reading sources from `raw` and writing targets to `input`.
+///
+/// `states` holds one state for each mapping.
+pub(super) fn apply(
+    mappings: &[Mapping],
+    states: &mut [MappingState],
+    raw: &DeviceInput,
+    input: &mut DeviceInput,
+) {
+    let mut sticks = [Stick::default(); 2];
+
+    for (mapping, state) in mappings.iter().zip(states) {
+        let value = mapping.from.value(raw);
+
+        match mapping.to {
+            Target::Button(button) => {
+                let pressed = state.update(
+                    mapping.actuation,
+                    mapping.from.threshold(),
+                    mapping.from.amount(raw),
+                );
+
+                if pressed {
+                    if let Some(buttons) = input.buttons_mut() {
+                        buttons.set(button, true);
+                    }
+                }
+            }
+            Target::Trigger(id) => {
+                if let Some(trigger) = input.get_mut(id) {
+                    trigger.0 = trigger.0.max(u8::from_f32(value));
+                }
+            }
+            Target::Stick { id, direction } => {
+                let vector = direction.vector() * value;
+                let stick = &mut sticks[id as usize];
+                stick.x += vector.x;
+                stick.y += vector.y;
+            }
+        }
+    }
+
+    for id in StickId::ALL {
+        let mapped = sticks[id as usize];
+
+        if mapped.length() <= f32::EPSILON {
+            continue;
+        }
+
+        if let Some(stick) = input.get_mut(id) {
+            stick.x += mapped.x;
+            stick.y += mapped.y;
+
+            if stick.length() > 1.0 {
+                *stick = stick.normalized();
+            }
+        }
+    }
+}
+
+/// Returns the absolute difference between two angles, between 0 and PI.
+fn angle_between(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(2.0 * PI);
+    difference.min(2.0 * PI - difference)
+}
@@ -0,0 +1,90 @@
+use einput_device::input::buttons::{Button, Buttons};
+use serde::{Deserialize, Serialize};
+
+/// How opposing D-pad directions held at the same time are resolved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Socd {
+    /// Left and Right
+    pub horizontal: SocdMode,
+    /// Up and Down
+    pub vertical: SocdMode,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SocdMode {
+    /// Both directions stay pressed.
+    #[default]
+    PassThrough,
+    /// Neither direction is pressed.
+    Neutral,
+    /// The direction pressed most recently wins.
+    LastWins,
+    /// The direction pressed first wins.
+    FirstWins,
+    /// Left or Up wins.
+    Priority,
+}
+
+impl SocdMode {
+    pub const ALL: [Self; 5] = [
+        Self::PassThrough,
+        Self::Neutral,
+        Self::LastWins,
+        Self::FirstWins,
+        Self::Priority,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SocdMode::PassThrough => "Pass Through",
+            SocdMode::Neutral => "Neutral",
+            SocdMode::LastWins => "Last Wins",
+            SocdMode::FirstWins => "First Wins",
+            SocdMode::Priority => "Left/Up Wins",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct SocdState {
+    held: [bool; 2],
+    /// Which of the pair was pressed most recently.
+    last: usize,
+}
+
+impl SocdState {
+    fn resolve(&mut self, mode: SocdMode, buttons: &mut Buttons, pair: [Button; 2]) {
+        let held = pair.map(|button| buttons.is_pressed(button));
+
+        for (i, (&held, &was_held)) in held.iter().zip(&self.held).enumerate() {
+            if held && !was_held {
+                self.last = i;
+            }
+        }
+
+        self.held = held;
+
+        if !(held[0] && held[1]) {
+            return;
+        }
+
+        let pressed = match mode {
+            SocdMode::PassThrough => [true, true],
+            SocdMode::Neutral => [false, false],
+            SocdMode::LastWins => [self.last == 0, self.last == 1],
+            SocdMode::FirstWins => [self.last == 1, self.last == 0],
+            SocdMode::Priority => [true, false],
+        };
+
+        for (button, pressed) in pair.into_iter().zip(pressed) {
+            buttons.set(button, pressed);
+        }
+    }
+}
+
+/// Resolves opposing directions in the output buttons.
+pub(super) fn apply(socd: &Socd, states: &mut [SocdState; 2], buttons: &mut Buttons) {
+    states[0].resolve(socd.horizontal, buttons, [Button::Left, Button::Right]);
+    states[1].resolve(socd.vertical, buttons, [Button::Up, Button::Down]);
+}
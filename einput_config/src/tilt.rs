@@ -0,0 +1,172 @@
+use std::f32::consts::PI;
+
+use einput_device::input::{
+    DeviceInput,
+    buttons::{Button, Buttons},
+    sticks::StickId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::curve::Curve;
+
+/// Moves a stick by tilting the device, for steering.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tilt {
+    pub stick: StickId,
+    pub source: TiltSource,
+    /// Tilt for the X and Y axes, or `None` to leave an axis alone.
+    pub axes: [Option<TiltAxis>; 2],
+    /// Source button that makes the current tilt the center.
+    pub recenter: Option<Button>,
+}
+
+impl Default for Tilt {
+    fn default() -> Self {
+        Self {
+            stick: StickId::Left,
+            source: TiltSource::Accelerometer,
+            axes: [Some(TiltAxis::default()), None],
+            recenter: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TiltSource {
+    /// Gravity measured directly, which is shaken by movement.
+    #[default]
+    Accelerometer,
+    /// Gravity from the device's fused orientation, which is steadier.
+    Orientation,
+}
+
+impl TiltSource {
+    pub const ALL: [Self; 2] = [Self::Accelerometer, Self::Orientation];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TiltSource::Accelerometer => "Accelerometer",
+            TiltSource::Orientation => "Orientation",
+        }
+    }
+
+    /// Returns the direction of gravity in the device's axes.
+    pub fn gravity(self, input: &DeviceInput) -> Option<[f32; 3]> {
+        match self {
+            TiltSource::Accelerometer => input.acceleration().map(|accel| [accel.x, accel.y, accel.z]),
+            TiltSource::Orientation => input.orientation().map(|orientation| orientation.gravity),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TiltAxis {
+    pub angle: TiltAngle,
+    /// Degrees from the center that fully push the stick.
+    pub max_angle: f32,
+    /// Degrees from the center that are ignored.
+    pub deadzone: f32,
+    pub curve: Curve,
+    pub invert: bool,
+}
+
+impl Default for TiltAxis {
+    fn default() -> Self {
+        Self {
+            angle: TiltAngle::Roll,
+            max_angle: 45.0,
+            deadzone: 2.0,
+            curve: Curve::Linear,
+            invert: false,
+        }
+    }
+}
+
+impl TiltAxis {
+    /// Returns the stick value for degrees from the center.
+    pub fn apply(&self, angle: f32) -> f32 {
+        let range = self.max_angle - self.deadzone;
+
+        if range <= 0.0 {
+            return 0.0;
+        }
+
+        let value = self.curve.apply((angle.abs() - self.deadzone) / range);
+        let value = value.copysign(angle);
+
+        match self.invert {
+            true => -value,
+            false => value,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TiltAngle {
+    /// Tilting left and right.
+    #[default]
+    Roll,
+    /// Tilting forwards and backwards.
+    Pitch,
+}
+
+impl TiltAngle {
+    pub const ALL: [Self; 2] = [Self::Roll, Self::Pitch];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TiltAngle::Roll => "Roll",
+            TiltAngle::Pitch => "Pitch",
+        }
+    }
+}
+
+/// Returns the roll and pitch of the device in degrees, from the direction of gravity.
+pub fn angles([x, y, z]: [f32; 3]) -> [f32; 2] {
+    let roll = x.atan2(y.hypot(z));
+    let pitch = z.atan2(x.hypot(y));
+
+    [roll, pitch].map(|angle| angle * 180.0 / PI)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct TiltState {
+    /// Roll and pitch at the center, taken from the first input or when recentering.
+    center: Option<[f32; 2]>,
+    held: bool,
+}
+
+impl TiltState {
+    /// Moves the configured stick in `input`, using the source buttons in `held`.
+    pub(super) fn apply(&mut self, tilt: &Tilt, input: &mut DeviceInput, held: Buttons) {
+        let Some(angles) = tilt.source.gravity(input).map(angles)
+        else { return };
+
+        let pressed = tilt.recenter.is_some_and(|button| held.is_pressed(button));
+
+        if pressed && !self.held {
+            self.center = None;
+        }
+
+        self.held = pressed;
+
+        let center = *self.center.get_or_insert(angles);
+
+        let Some(stick) = input.get_mut(tilt.stick)
+        else { return };
+
+        for (value, axis) in [&mut stick.x, &mut stick.y].into_iter().zip(&tilt.axes) {
+            let Some(axis) = axis
+            else { continue };
+
+            let angle = match axis.angle {
+                TiltAngle::Roll => angles[0] - center[0],
+                TiltAngle::Pitch => angles[1] - center[1],
+            };
+
+            *value = (*value + axis.apply(angle)).clamp(-1.0, 1.0);
+        }
+    }
+}
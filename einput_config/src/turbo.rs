@@ -0,0 +1,87 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Repeatedly presses and releases a button.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Turbo {
+    /// Presses per second
+    pub rate: f32,
+    /// Fraction of each press spent held down, between 0.0 and 1.0
+    pub duty: f32,
+    pub mode: TurboMode,
+}
+
+impl Default for Turbo {
+    fn default() -> Self {
+        Self {
+            rate: 10.0,
+            duty: 0.5,
+            mode: TurboMode::Hold,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurboMode {
+    /// Fires while the button is held.
+    #[default]
+    Hold,
+    /// Pressing the button starts firing, and pressing it again stops.
+    Toggle,
+}
+
+impl TurboMode {
+    pub const ALL: [Self; 2] = [Self::Hold, Self::Toggle];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TurboMode::Hold => "Hold",
+            TurboMode::Toggle => "Toggle",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TurboState {
+    held: bool,
+    /// When firing started, or `None` if not firing.
+    start: Option<Instant>,
+}
+
+impl TurboState {
+    /// Returns whether the button should be pressed at `now`.
+    pub fn update(&mut self, turbo: &Turbo, held: bool, now: Instant) -> bool {
+        let pressed = held && !self.held;
+        self.held = held;
+
+        match turbo.mode {
+            TurboMode::Hold => match held {
+                true => {
+                    self.start.get_or_insert(now);
+                }
+                false => self.start = None,
+            },
+            TurboMode::Toggle => {
+                if pressed {
+                    self.start = match self.start {
+                        Some(_) => None,
+                        None => Some(now),
+                    };
+                }
+            }
+        }
+
+        let Some(start) = self.start
+        else { return false };
+
+        if turbo.rate <= 0.0 {
+            return true;
+        }
+
+        let period = 1.0 / turbo.rate;
+        let phase = (now - start).as_secs_f32() % period;
+
+        phase < period * turbo.duty
+    }
+}
@@ -1,17 +1,27 @@
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
 };
 
-use einput_device::{DeviceId, DeviceInfo, DeviceInput};
-use einput_util::shared::{Reader, Writer};
+use einput_device::{DeviceId, DeviceInfo, DeviceInput, DeviceOutput};
+use einput_util::{
+    fusion::Fusion,
+    shared::{Reader, Writer},
+};
 
 pub type DeviceReader = Reader<DeviceId, DeviceInput>;
 pub type DeviceWriter = Writer<DeviceId, DeviceInput>;
 
+pub type DeviceOutputReader = Reader<DeviceId, DeviceOutput>;
+pub type DeviceOutputWriter = Writer<DeviceId, DeviceOutput>;
+
 pub struct DeviceTransformer {
     func: Box<dyn FnMut(&mut DeviceInput) + Send + Sync>,
     provider: Arc<dyn Fn() -> Box<dyn FnMut(&mut DeviceInput) + Send + Sync> + Send + Sync + 'static>,
+    tick: bool,
 }
 
 impl DeviceTransformer {
@@ -21,9 +31,22 @@ impl DeviceTransformer {
         DeviceTransformer {
             func,
             provider: Arc::new(provider),
+            tick: false,
         }
     }
 
+    /// Runs the transformer on the latest input at a steady rate, even when the device sends no new input.
+    ///
+    /// Needed by transformers that change their output over time.
+    pub fn with_tick(mut self, tick: bool) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    pub fn tick(&self) -> bool {
+        self.tick
+    }
+
     fn call(&mut self, input: &mut DeviceInput) {
         (self.func)(input)
     }
@@ -32,7 +55,7 @@ impl DeviceTransformer {
 impl Clone for DeviceTransformer {
     fn clone(&self) -> Self {
         let func = (self.provider)();
-        Self { func, provider: self.provider.clone() }
+        Self { func, provider: self.provider.clone(), tick: self.tick }
     }
 }
 
@@ -50,8 +73,12 @@ pub struct Device {
 
     pub(crate) transformer: Arc<Mutex<DeviceTransformer>>,
 
+    input: Arc<Mutex<DeviceState>>,
     input_writer: DeviceWriter,
     input_writer_raw: DeviceWriter,
+
+    output: Arc<Mutex<DeviceOutput>>,
+    output_writer: DeviceOutputWriter,
 }
 
 impl Device {
@@ -59,6 +86,8 @@ impl Device {
         let transformer: Arc<Mutex<DeviceTransformer>> = Arc::new(Mutex::new(transformer));
         let input_writer = Writer::new();
         let input_writer_raw = Writer::new();
+        let output = Arc::new(Mutex::new(DeviceOutput::new(&info.output)));
+        let input = Arc::new(Mutex::new(DeviceState::new(&info)));
 
         Device {
             info: Arc::new(Mutex::new(info)),
@@ -67,13 +96,17 @@ impl Device {
 
             transformer,
 
+            input,
             input_writer,
             input_writer_raw,
+
+            output,
+            output_writer: Writer::new(),
         }
     }
 
     pub(crate) fn replace(&self, info: DeviceInfo) -> Option<DeviceOwner> {
-        let mut owner = self.create_owner()?;
+        let owner = self.create_owner()?;
 
         let mut self_info = self.info.lock().unwrap();
 
@@ -81,9 +114,12 @@ impl Device {
             return Some(owner);
         }
 
+        if self_info.output != info.output {
+            *self.output.lock().unwrap() = DeviceOutput::new(&info.output);
+        }
+
         *self_info = info.clone();
-        owner.input = DeviceInput::new(&self_info.input);
-        owner.input_raw = owner.input.clone();
+        *self.input.lock().unwrap() = DeviceState::new(&self_info);
 
         Some(owner)
     }
@@ -95,11 +131,13 @@ impl Device {
 
         let self_info = self.info.lock().unwrap();
 
-        let input = DeviceInput::new(&self_info.input);
+        *self.input.lock().unwrap() = DeviceState::new(&self_info);
+
+        let mut output_reader = DeviceOutputReader::new();
+        self.output_writer.register(&mut output_reader);
 
         Some(DeviceOwner {
-            input_raw: input.clone(),
-            input,
+            input: self.input.clone(),
             id: self_info.id().clone(),
             
             transformer: self.transformer.clone(),
@@ -108,6 +146,8 @@ impl Device {
 
             writer: self.input_writer.clone(),
             writer_raw: self.input_writer_raw.clone(),
+
+            output_reader,
         })
     }
 
@@ -126,11 +166,74 @@ impl Device {
     pub fn register_reader_raw(&self, reader: &mut DeviceReader) {
         self.input_writer_raw.register(reader);
     }
+
+    /// Runs the transformer again on the latest raw input, if it needs to be ticked.
+    pub(crate) fn tick(&self) {
+        if !self.owned() {
+            return;
+        }
+
+        let id = self.info.lock().unwrap().id().clone();
+
+        let mut state = self.input.lock().unwrap();
+        let mut transformer = self.transformer.lock().expect("device transformer poisoned");
+
+        if !transformer.tick() {
+            return;
+        }
+
+        let DeviceState { input, input_raw, .. } = &mut *state;
+        input.clone_from(input_raw);
+        transformer.call(input);
+        self.input_writer.write(&id, input);
+    }
+
+    /// Used by outputs to send feedback, like rumble, back to the device's owner.
+    pub fn update_output(&self, f: impl FnOnce(&mut DeviceOutput)) {
+        let id = self.info.lock().unwrap().id().clone();
+
+        let mut output = self.output.lock().unwrap();
+        f(&mut output);
+        self.output_writer.write(&id, &output);
+    }
 }
 
-pub struct DeviceOwner {
+struct DeviceState {
     input: DeviceInput,
     input_raw: DeviceInput,
+    fusion: Fusion,
+}
+
+impl DeviceState {
+    fn new(info: &DeviceInfo) -> Self {
+        let input = DeviceInput::new(&info.input);
+
+        DeviceState {
+            input_raw: input.clone(),
+            input,
+            fusion: Fusion::default(),
+        }
+    }
+
+    /// Updates the raw input's orientation from its gyroscope and accelerometer.
+    fn fuse(&mut self) {
+        let input = &mut self.input_raw;
+
+        let (Some(timestamp), Some(gyro), Some(accel)) = (input.timestamp(), input.gyroscope(), input.acceleration())
+        else { return };
+
+        // Pitch, yaw and roll turn about the X, Y and Z axes
+        self.fusion.update(timestamp, [gyro.pitch, gyro.yaw, gyro.roll], [accel.x, accel.y, accel.z]);
+
+        if let Some(orientation) = input.orientation_mut() {
+            orientation.quaternion = self.fusion.quaternion();
+            orientation.gravity = self.fusion.gravity();
+        }
+    }
+}
+
+pub struct DeviceOwner {
+    input: Arc<Mutex<DeviceState>>,
     id: DeviceId,
 
     transformer: Arc<Mutex<DeviceTransformer>>,
@@ -139,20 +242,35 @@ pub struct DeviceOwner {
 
     writer: Writer<DeviceId, DeviceInput>,
     writer_raw: Writer<DeviceId, DeviceInput>,
+
+    output_reader: DeviceOutputReader,
 }
 
 impl DeviceOwner {
+    /// Timestamps the input with the current time, which `f` can replace with the device's own.
     pub fn update(&mut self, f: impl FnOnce(&mut DeviceInput)) {
-        f(&mut self.input_raw);
-        self.writer_raw.write(&self.id, &self.input_raw);
+        let mut state = self.input.lock().unwrap();
+
+        state.input_raw.set_timestamp(Some(Instant::now()));
+        f(&mut state.input_raw);
+        state.fuse();
+
+        let DeviceState { input, input_raw, .. } = &mut *state;
 
-        self.input.clone_from(&self.input_raw);
+        self.writer_raw.write(&self.id, input_raw);
+
+        input.clone_from(input_raw);
         self
             .transformer
             .lock()
             .expect("device transformer poisoned")
-            .call(&mut self.input);
-        self.writer.write(&self.id, &self.input);
+            .call(input);
+        self.writer.write(&self.id, input);
+    }
+
+    /// The latest output sent to this device, if any output has been sent yet.
+    pub fn output(&mut self) -> Option<&DeviceOutput> {
+        self.output_reader.update().get(&self.id)
     }
 }
 
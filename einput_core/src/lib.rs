@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
 };
 
 use einput_device::{DeviceId, DeviceInfo, DeviceKind};
@@ -16,7 +17,12 @@ pub struct EInput(Arc<Mutex<Inner>>);
 
 impl EInput {
     pub fn new() -> Self {
-        EInput(Arc::new(Mutex::new(Inner::new())))
+        let inner = Arc::new(Mutex::new(Inner::new()));
+
+        let weak = Arc::downgrade(&inner);
+        std::thread::spawn(move || tick(weak));
+
+        EInput(inner)
     }
 
     pub fn get_or_create(&self, id: DeviceId) -> Device {
@@ -71,6 +77,25 @@ impl EInput {
     }
 }
 
+/// How often transformers that need ticking are run without new input.
+const TICK_INTERVAL: Duration = Duration::from_millis(4);
+
+fn tick(inner: Weak<Mutex<Inner>>) {
+    loop {
+        std::thread::sleep(TICK_INTERVAL);
+
+        let Some(inner) = inner.upgrade()
+        else { return };
+
+        let devices: Vec<Device> = inner.lock().unwrap().devices.values().cloned().collect();
+        drop(inner);
+
+        for device in devices {
+            device.tick();
+        }
+    }
+}
+
 struct Inner {
     devices: HashMap<DeviceId, Device>,
     transformers: HashMap<DeviceId, DeviceTransformer>,
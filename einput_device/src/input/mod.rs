@@ -1,4 +1,4 @@
-use std::{alloc::Layout, fmt::Debug, ptr::NonNull};
+use std::{alloc::Layout, fmt::Debug, ptr::NonNull, time::Instant};
 
 use einput_util::axis::{Stick, Trigger};
 
@@ -11,6 +11,7 @@ use self::{
     acceleration::Acceleration,
     buttons::Buttons,
     gyroscope::Gyroscope,
+    orientation::Orientation,
     sticks::Sticks,
     triggers::Triggers,
 };
@@ -18,6 +19,7 @@ use self::{
 pub mod acceleration;
 pub mod buttons;
 pub mod gyroscope;
+pub mod orientation;
 pub mod sticks;
 pub mod triggers;
 
@@ -31,6 +33,7 @@ const ALIGNMENT: usize = {
         a::<Acceleration>(),
         a::<Buttons>(),
         a::<Gyroscope>(),
+        a::<Orientation>(),
         a::<Stick>(),
         a::<Trigger>(),
     ];
@@ -51,9 +54,11 @@ const ALIGNMENT: usize = {
 
 #[derive(Clone, Copy, Debug)]
 struct Header {
+    timestamp: Option<Instant>,
     acceleration: Option<Offset<Acceleration>>,
     buttons: Option<Offset<Buttons>>,
     gyroscope: Option<Offset<Gyroscope>>,
+    orientation: Option<Offset<Orientation>>,
     sticks: Option<Offset<Sticks>>,
     triggers: Option<Offset<Triggers>>,
 }
@@ -113,6 +118,10 @@ impl Debug for DeviceInput {
 
         builder.field("size", &self.size);
 
+        if let Some(timestamp) = self.timestamp() {
+            builder.field("timestamp", &timestamp);
+        }
+
         if let Some(accel) = self.acceleration() {
             builder.field("acceleration", accel);
         }
@@ -125,6 +134,10 @@ impl Debug for DeviceInput {
             builder.field("gyroscope", gyro);
         }
 
+        if let Some(orientation) = self.orientation() {
+            builder.field("orientation", orientation);
+        }
+
         if let Some(sticks) = self.sticks() {
             builder.field("sticks", sticks);
         }
@@ -143,9 +156,11 @@ impl DeviceInput {
 
         let header_offset = builder.write::<Header>();
         let header = Header {
+            timestamp: None,
             acceleration: builder.write_maybe(info.acceleration),
             buttons: builder.write_maybe(info.buttons.get_pressed().next().is_some()),
             gyroscope: builder.write_maybe(info.gyroscope),
+            orientation: builder.write_maybe(info.gyroscope && info.acceleration),
             sticks: builder.write_maybe(info.sticks),
             triggers: builder.write_maybe(info.triggers),
         };
@@ -176,6 +191,10 @@ impl DeviceInput {
                 gyroscope.write(ptr.as_ptr());
             }
 
+            if let Some(orientation) = header.orientation {
+                orientation.write(ptr.as_ptr());
+            }
+
             if let Some(sticks) = header.sticks {
                 sticks.write(ptr.as_ptr());
             }
@@ -192,6 +211,19 @@ impl DeviceInput {
         unsafe { &*self.ptr.as_ptr().cast() }
     }
 
+    fn header_mut(&mut self) -> &mut Header {
+        unsafe { &mut *self.ptr.as_ptr().cast() }
+    }
+
+    /// When the device sent this input, if known.
+    pub fn timestamp(&self) -> Option<Instant> {
+        self.header().timestamp
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: Option<Instant>) {
+        self.header_mut().timestamp = timestamp;
+    }
+
     pub fn get<'a, I>(&'a self, index: I) -> I::Output<'a>
     where
         I: DeviceIndex<Self>,
@@ -213,6 +245,7 @@ super::component_accessors! {
         acceleration: Acceleration;
         buttons: Buttons;
         gyroscope: Gyroscope;
+        orientation: Orientation;
         sticks: Sticks;
         triggers: Triggers;
     }
@@ -0,0 +1,17 @@
+/// Fused from the gyroscope and accelerometer by einput, for devices that have both.
+#[derive(Clone, Copy, Debug)]
+pub struct Orientation {
+    /// `[w, x, y, z]`, drifts slowly around gravity since the accelerometer can't correct yaw.
+    pub quaternion: [f32; 4],
+    /// Direction of gravity in the device's axes, with a length of 1.
+    pub gravity: [f32; 3],
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self {
+            quaternion: [1.0, 0.0, 0.0, 0.0],
+            gravity: [0.0, 0.0, 1.0],
+        }
+    }
+}
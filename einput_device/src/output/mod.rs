@@ -154,3 +154,6 @@ super::component_accessors! {
         rumble: Rumble;
     }
 }
+
+unsafe impl Send for DeviceOutput {}
+unsafe impl Sync for DeviceOutput {}
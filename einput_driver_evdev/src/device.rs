@@ -0,0 +1,427 @@
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    os::fd::AsRawFd,
+    path::Path,
+    time::{Instant, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use einput_core::{device::DeviceOwner, EInput};
+use einput_device::{
+    input::{
+        buttons::{Button, Buttons},
+        sticks::StickId,
+        triggers::TriggerId,
+    },
+    DeviceInfo, DeviceInput, DeviceInputInfo, DeviceKind, DeviceOutputInfo,
+};
+use einput_util::axis::TriggerAxis;
+use evdev::{
+    AbsInfo, AbsoluteAxisCode, EventSummary, FFEffect, FFEffectCode, FFEffectData, FFEffectKind,
+    FFReplay, FFTrigger, InputEvent, KeyCode, SynchronizationCode,
+};
+
+/// Milliseconds to wait for input before checking for new rumble output.
+const POLL_TIMEOUT: i32 = 16;
+
+// Matches the codes used by the xpad driver, where BTN_NORTH and BTN_WEST are the kernel's BTN_X and BTN_Y.
+const KEYS: [(KeyCode, Button); 17] = [
+    (KeyCode::BTN_SOUTH, Button::A),
+    (KeyCode::BTN_EAST, Button::B),
+    (KeyCode::BTN_NORTH, Button::X),
+    (KeyCode::BTN_WEST, Button::Y),
+    (KeyCode::BTN_TL, Button::L1),
+    (KeyCode::BTN_TR, Button::R1),
+    (KeyCode::BTN_TL2, Button::L2),
+    (KeyCode::BTN_TR2, Button::R2),
+    (KeyCode::BTN_SELECT, Button::Select),
+    (KeyCode::BTN_START, Button::Start),
+    (KeyCode::BTN_MODE, Button::Home),
+    (KeyCode::BTN_THUMBL, Button::LStick),
+    (KeyCode::BTN_THUMBR, Button::RStick),
+    (KeyCode::BTN_DPAD_UP, Button::Up),
+    (KeyCode::BTN_DPAD_DOWN, Button::Down),
+    (KeyCode::BTN_DPAD_LEFT, Button::Left),
+    (KeyCode::BTN_DPAD_RIGHT, Button::Right),
+];
+
+// Joysticks without the gamepad range number their buttons from BTN_TRIGGER up to BTN_BASE6.
+const JOYSTICK_KEYS: [(KeyCode, Button); 12] = [
+    (KeyCode::BTN_TRIGGER, Button::A),
+    (KeyCode::BTN_THUMB, Button::B),
+    (KeyCode::BTN_THUMB2, Button::X),
+    (KeyCode::BTN_TOP, Button::Y),
+    (KeyCode::BTN_TOP2, Button::L1),
+    (KeyCode::BTN_PINKIE, Button::R1),
+    (KeyCode::BTN_BASE, Button::L2),
+    (KeyCode::BTN_BASE2, Button::R2),
+    (KeyCode::BTN_BASE3, Button::Select),
+    (KeyCode::BTN_BASE4, Button::Start),
+    (KeyCode::BTN_BASE5, Button::LStick),
+    (KeyCode::BTN_BASE6, Button::RStick),
+];
+
+pub struct DeviceDriver {
+    device: evdev::Device,
+    owner: DeviceOwner,
+    mapping: Mapping,
+
+    effect: Option<FFEffect>,
+    rumble: (u8, u8),
+}
+
+impl DeviceDriver {
+    /// Returns `None` if the device isn't a gamepad or joystick.
+    pub fn new(einput: EInput, device: evdev::Device, path: &Path) -> Result<Option<Self>> {
+        let name = device.name().unwrap_or("Unknown Device").to_owned();
+
+        // Virtual gamepads created by einput's uinput output would otherwise feed back into einput.
+        if name.starts_with("einput ") {
+            return Ok(None);
+        }
+
+        let Some(keys) = device.supported_keys()
+        else { return Ok(None) };
+
+        // BTN_SOUTH and BTN_TRIGGER are the first codes of the kernel's gamepad and joystick ranges.
+        if !keys.contains(KeyCode::BTN_SOUTH) && !keys.contains(KeyCode::BTN_TRIGGER) {
+            return Ok(None);
+        }
+
+        let mapping = Mapping::new(&device)?;
+
+        let rumble_motors = match device.supported_ff() {
+            Some(ff) if ff.contains(FFEffectCode::FF_RUMBLE) => 2,
+            _ => 0,
+        };
+
+        let id = [device.unique_name(), device.physical_path()]
+            .into_iter()
+            .flatten()
+            .find(|id| !id.is_empty())
+            .map(str::to_owned)
+            .unwrap_or_else(|| path.display().to_string());
+
+        let info = DeviceInfo::new(
+            name.clone(),
+            name,
+            format!("evdev::{id}").into(),
+            DeviceKind::Gamepad,
+        )
+            .with_input(mapping.info())
+            .with_output(DeviceOutputInfo { rumble_motors });
+
+        let owner = einput.create_device(info)
+            .context("device already exists")?;
+
+        let mut driver = Self {
+            device,
+            owner,
+            mapping,
+            effect: None,
+            rumble: (0, 0),
+        };
+
+        driver.sync()?;
+
+        Ok(Some(driver))
+    }
+
+    /// Reads the full state of the device instead of applying events.
+    fn sync(&mut self) -> Result<()> {
+        let keys = self.device.get_key_state()?;
+        let axes: Vec<(AbsoluteAxisCode, AbsInfo)> = self.device.get_absinfo()?.collect();
+
+        let mapping = &self.mapping;
+
+        self.owner.update(|input| {
+            for &key in mapping.keys.keys() {
+                mapping.key(input, key, keys.contains(key) as i32);
+            }
+
+            for (axis, info) in axes {
+                mapping.abs(input, axis, info.value());
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn run(mut self) -> Result<()> {
+        self.device.set_nonblocking(true)?;
+
+        loop {
+            self.wait()?;
+            self.read()?;
+            self.write_rumble()?;
+        }
+    }
+
+    fn wait(&self) -> Result<()> {
+        let mut fd = libc::pollfd {
+            fd: self.device.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        if unsafe { libc::poll(&mut fd, 1, POLL_TIMEOUT) } < 0 {
+            let error = std::io::Error::last_os_error();
+            if error.kind() != ErrorKind::Interrupted {
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<()> {
+        let events: Vec<InputEvent> = match self.device.fetch_events() {
+            Ok(events) => events.collect(),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        // Events after a dropped report only describe part of the changes
+        let dropped = events.iter().any(|event| {
+            matches!(
+                event.destructure(),
+                EventSummary::Synchronization(_, SynchronizationCode::SYN_DROPPED, _)
+            )
+        });
+
+        if dropped {
+            return self.sync();
+        }
+
+        let mapping = &self.mapping;
+        let timestamp = events.last().and_then(|event| instant(event.timestamp()));
+
+        self.owner.update(|input| {
+            if timestamp.is_some() {
+                input.set_timestamp(timestamp);
+            }
+
+            for event in events {
+                match event.destructure() {
+                    EventSummary::Key(_, key, value) => mapping.key(input, key, value),
+                    EventSummary::AbsoluteAxis(_, axis, value) => mapping.abs(input, axis, value),
+                    _ => {}
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn write_rumble(&mut self) -> Result<()> {
+        let rumble = match self.owner.output().map(|output| output.rumbles()) {
+            Some([strong, weak, ..]) => (strong.strength, weak.strength),
+            _ => (0, 0),
+        };
+
+        if rumble == self.rumble {
+            return Ok(());
+        }
+
+        self.rumble = rumble;
+
+        let data = FFEffectData {
+            direction: 0,
+            trigger: FFTrigger::default(),
+            // A length of 0 plays until the effect is stopped.
+            replay: FFReplay { length: 0, delay: 0 },
+            kind: FFEffectKind::Rumble {
+                strong_magnitude: rumble.0 as u16 * 257,
+                weak_magnitude: rumble.1 as u16 * 257,
+            },
+        };
+
+        let effect = match &mut self.effect {
+            Some(effect) => {
+                effect.update(data)?;
+                effect
+            }
+            None => self.effect.insert(self.device.upload_ff_effect(data)?),
+        };
+
+        match rumble {
+            (0, 0) => effect.stop()?,
+            _ => effect.play(1)?,
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    StickX(StickId),
+    StickY(StickId),
+    Trigger(TriggerId),
+    HatX,
+    HatY,
+}
+
+#[derive(Clone, Copy)]
+struct AbsAxis {
+    axis: Axis,
+    min: i32,
+    max: i32,
+}
+
+impl AbsAxis {
+    /// Returns a value between 0.0 and 1.0
+    fn normalize(&self, value: i32) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+
+        ((value - self.min) as f32 / (self.max - self.min) as f32).clamp(0.0, 1.0)
+    }
+}
+
+struct Mapping {
+    buttons: Buttons,
+    keys: HashMap<KeyCode, Button>,
+    axes: HashMap<AbsoluteAxisCode, AbsAxis>,
+}
+
+impl Mapping {
+    fn new(device: &evdev::Device) -> Result<Self> {
+        let supported = device.supported_keys();
+
+        let supports = |key: KeyCode| supported.is_some_and(|keys| keys.contains(key));
+
+        let joystick_keys = match supports(KeyCode::BTN_SOUTH) {
+            true => [].as_slice(),
+            false => JOYSTICK_KEYS.as_slice(),
+        };
+
+        let keys: HashMap<KeyCode, Button> = KEYS
+            .iter()
+            .chain(joystick_keys)
+            .copied()
+            .filter(|&(key, _)| supports(key))
+            .collect();
+
+        let mut buttons = Buttons::default();
+        for &button in keys.values() {
+            buttons |= button;
+        }
+
+        let absinfo: HashMap<AbsoluteAxisCode, AbsInfo> = match device.supported_absolute_axes() {
+            Some(_) => device.get_absinfo()?.collect(),
+            None => HashMap::new(),
+        };
+
+        // Gamepads with a right stick on RX/RY use Z/RZ for the triggers,
+        // while most generic HID gamepads put the right stick on Z/RZ.
+        let z_is_trigger = absinfo.contains_key(&AbsoluteAxisCode::ABS_RX);
+
+        let mut axes = HashMap::new();
+
+        for (&code, info) in &absinfo {
+            let axis = match code {
+                AbsoluteAxisCode::ABS_X => Axis::StickX(StickId::Left),
+                AbsoluteAxisCode::ABS_Y => Axis::StickY(StickId::Left),
+                AbsoluteAxisCode::ABS_RX => Axis::StickX(StickId::Right),
+                AbsoluteAxisCode::ABS_RY => Axis::StickY(StickId::Right),
+                AbsoluteAxisCode::ABS_Z if z_is_trigger => Axis::Trigger(TriggerId::L2),
+                AbsoluteAxisCode::ABS_RZ if z_is_trigger => Axis::Trigger(TriggerId::R2),
+                AbsoluteAxisCode::ABS_Z => Axis::StickX(StickId::Right),
+                AbsoluteAxisCode::ABS_RZ => Axis::StickY(StickId::Right),
+                AbsoluteAxisCode::ABS_BRAKE => Axis::Trigger(TriggerId::L2),
+                AbsoluteAxisCode::ABS_GAS => Axis::Trigger(TriggerId::R2),
+                AbsoluteAxisCode::ABS_HAT0X => Axis::HatX,
+                AbsoluteAxisCode::ABS_HAT0Y => Axis::HatY,
+                _ => continue,
+            };
+
+            if matches!(axis, Axis::HatX) {
+                buttons |= Button::Left;
+                buttons |= Button::Right;
+            } else if matches!(axis, Axis::HatY) {
+                buttons |= Button::Up;
+                buttons |= Button::Down;
+            }
+
+            axes.insert(code, AbsAxis {
+                axis,
+                min: info.minimum(),
+                max: info.maximum(),
+            });
+        }
+
+        Ok(Self { buttons, keys, axes })
+    }
+
+    fn info(&self) -> DeviceInputInfo {
+        let axes = || self.axes.values().map(|axis| axis.axis);
+
+        DeviceInputInfo {
+            buttons: self.buttons,
+            sticks: axes().any(|axis| matches!(axis, Axis::StickX(_) | Axis::StickY(_))),
+            triggers: axes().any(|axis| matches!(axis, Axis::Trigger(_))),
+            ..Default::default()
+        }
+    }
+
+    fn key(&self, input: &mut DeviceInput, key: KeyCode, value: i32) {
+        let Some(&button) = self.keys.get(&key)
+        else { return };
+
+        // A value of 2 is a key repeat, which still means the key is held.
+        if let Some(buttons) = input.buttons_mut() {
+            buttons.set(button, value != 0);
+        }
+    }
+
+    fn abs(&self, input: &mut DeviceInput, code: AbsoluteAxisCode, value: i32) {
+        let Some(axis) = self.axes.get(&code)
+        else { return };
+
+        let normalized = axis.normalize(value);
+
+        match axis.axis {
+            Axis::StickX(id) => {
+                if let Some(sticks) = input.sticks_mut() {
+                    sticks.get_mut(id).x = normalized * 2.0 - 1.0;
+                }
+            }
+            Axis::StickY(id) => {
+                if let Some(sticks) = input.sticks_mut() {
+                    sticks.get_mut(id).y = normalized * 2.0 - 1.0;
+                }
+            }
+            Axis::Trigger(id) => {
+                if let Some(triggers) = input.triggers_mut() {
+                    *triggers.get_mut(id) = u8::from_f32(normalized).into();
+                }
+            }
+            Axis::HatX => {
+                if let Some(buttons) = input.buttons_mut() {
+                    buttons.set(Button::Left, value < 0);
+                    buttons.set(Button::Right, value > 0);
+                }
+            }
+            Axis::HatY => {
+                if let Some(buttons) = input.buttons_mut() {
+                    buttons.set(Button::Up, value < 0);
+                    buttons.set(Button::Down, value > 0);
+                }
+            }
+        }
+    }
+}
+
+/// Converts a kernel event time to an [`Instant`], or `None` if it's in the future.
+fn instant(time: SystemTime) -> Option<Instant> {
+    let age = SystemTime::now().duration_since(time).ok()?;
+
+    Instant::now().checked_sub(age)
+}
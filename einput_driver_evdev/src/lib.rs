@@ -0,0 +1,133 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use einput_core::EInput;
+use inotify::{Inotify, WatchMask};
+use log::{debug, warn};
+
+use self::device::DeviceDriver;
+
+mod device;
+
+const DEV_INPUT: &str = "/dev/input";
+
+pub fn start(einput: EInput) {
+    let open = Open::default();
+
+    match std::fs::read_dir(DEV_INPUT) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                scan(&einput, entry.path(), &open);
+            }
+        }
+        Err(e) => {
+            warn!("error enumerating {DEV_INPUT}: {e}");
+        }
+    }
+
+    std::thread::spawn(move || match watch(&einput, &open) {
+        Ok(()) => {}
+        Err(e) => warn!("error watching {DEV_INPUT} for new devices: {e}"),
+    });
+}
+
+/// New device nodes are usually only readable after udev sets their permissions,
+/// so attribute changes are watched as well as creation.
+fn watch(einput: &EInput, open: &Open) -> Result<()> {
+    let mut inotify = Inotify::init()?;
+    inotify
+        .watches()
+        .add(DEV_INPUT, WatchMask::CREATE | WatchMask::ATTRIB)?;
+
+    let mut buffer = [0; 4096];
+
+    loop {
+        for event in inotify.read_events_blocking(&mut buffer)? {
+            let Some(name) = event.name
+            else { continue };
+
+            scan(einput, Path::new(DEV_INPUT).join(name), open);
+        }
+    }
+}
+
+fn scan(einput: &EInput, path: PathBuf, open: &Open) {
+    let is_event = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("event"));
+
+    if !is_event {
+        return;
+    }
+
+    let Some(guard) = open.insert(path.clone())
+    else { return };
+
+    let einput = einput.clone();
+
+    std::thread::spawn(move || {
+        let _guard = guard;
+
+        let device = match evdev::Device::open(&path) {
+            Ok(device) => device,
+            Err(e) => {
+                debug!("error opening {}: {e}", path.display());
+                return;
+            }
+        };
+
+        let driver = match DeviceDriver::new(einput, device, &path) {
+            Ok(Some(driver)) => driver,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("error creating device for {}: {e}", path.display());
+                return;
+            }
+        };
+
+        match driver.run() {
+            Ok(()) => {}
+            Err(e) => {
+                debug!("{} disconnected: {e}", path.display());
+            }
+        }
+    });
+}
+
+/// Paths that currently have a thread reading them.
+#[derive(Clone, Default)]
+struct Open {
+    inner: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl Open {
+    fn insert(&self, path: PathBuf) -> Option<OpenGuard> {
+        if !self.inner.lock().expect("Open poisoned").insert(path.clone()) {
+            return None;
+        }
+
+        Some(OpenGuard {
+            path,
+            inner: self.inner.clone(),
+        })
+    }
+}
+
+struct OpenGuard {
+    path: PathBuf,
+    inner: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl Drop for OpenGuard {
+    fn drop(&mut self) {
+        let Ok(mut lock) = self.inner.lock()
+        else { return };
+
+        lock.remove(&self.path);
+    }
+}
@@ -13,6 +13,7 @@ const EP_IN: u8 = 0x81;
 const EP_OUT: u8 = 0x02;
 
 const INITIALIZE: [u8; 1] = [0x13];
+const RUMBLE: u8 = 0x11;
 
 const STATE_NORMAL: u8 = 0x10;
 const STATE_WAVEBIRD: u8 = 0x20;
@@ -25,6 +26,7 @@ pub struct DeviceDriver {
 
     packet: InputPacket,
     controllers: [Option<Controller>; 4],
+    rumble: [bool; 4],
 }
 
 impl DeviceDriver {
@@ -36,6 +38,7 @@ impl DeviceDriver {
             serial,
             packet: InputPacket::zeroed(),
             controllers: [None, None, None, None],
+            rumble: [false; 4],
         }
     }
 
@@ -97,8 +100,28 @@ impl DeviceDriver {
             }
         }
 
+        self.write_rumble();
+
         Ok(())
     }
+
+    fn write_rumble(&mut self) {
+        let rumble = std::array::from_fn(|i| match &mut self.controllers[i] {
+            Some(con) => con.rumble(),
+            None => false,
+        });
+
+        if rumble == self.rumble {
+            return;
+        }
+
+        let packet = [RUMBLE, rumble[0] as u8, rumble[1] as u8, rumble[2] as u8, rumble[3] as u8];
+
+        match self.device.write_interrupt(EP_OUT, &packet, Duration::from_millis(16)) {
+            Ok(_) => self.rumble = rumble,
+            Err(e) => warn!("error writing rumble: {e}"),
+        }
+    }
 }
 
 struct Controller {
@@ -152,6 +175,14 @@ impl Controller {
             triggers.r2 = packet.rt.into();
         });
     }
+
+    /// The adapter can only turn each motor on or off.
+    fn rumble(&mut self) -> bool {
+        self.device
+            .output()
+            .and_then(|output| output.rumbles().first())
+            .is_some_and(|rumble| rumble.strength > 0)
+    }
 }
 
 #[repr(C)]
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, sync::{Arc, Mutex}, time::Duration};
 
 use anyhow::Result;
-use dsu::{packet::{Button as DsuButton, ControllerInfo, SendControllerData}, server::Server};
+use dsu::{packet::{Button as DsuButton, ControllerInfo, SendControllerData}, server::{Server, ServerConfig}};
 use einput_core::{device::{Device, DeviceReader}, output::Output};
 use einput_device::{input::buttons::Button, DeviceId, DeviceInput};
 use einput_util::axis::StickAxis;
@@ -16,16 +16,60 @@ struct DeviceList {
     changed: bool,
 }
 
+/// Server config shared with the server thread, which applies changes while running.
+#[derive(Clone, Default)]
+pub struct DsuConfig(Arc<Mutex<ConfigState>>);
+
+#[derive(Default)]
+struct ConfigState {
+    config: ServerConfig,
+    changed: bool,
+}
+
+impl DsuConfig {
+    pub fn new(config: ServerConfig) -> Self {
+        Self(Arc::new(Mutex::new(ConfigState { config, changed: false })))
+    }
+
+    pub fn get(&self) -> ServerConfig {
+        self.0.lock().unwrap().config.clone()
+    }
+
+    pub fn set(&self, config: ServerConfig) {
+        let mut lock = self.0.lock().unwrap();
+        lock.config = config;
+        lock.changed = true;
+    }
+
+    fn take_changed(&self) -> Option<ServerConfig> {
+        let mut lock = self.0.lock().unwrap();
+        std::mem::take(&mut lock.changed).then(|| lock.config.clone())
+    }
+}
+
 pub struct DsuOutput {
     devices: Devices,
+    config: DsuConfig,
 }
 
 impl DsuOutput {
     pub fn new() -> Self {
+        Self::with_config(ServerConfig::default())
+    }
+
+    pub fn with_config(config: ServerConfig) -> Self {
+        Self::with_shared_config(DsuConfig::new(config))
+    }
+
+    pub fn with_shared_config(config: DsuConfig) -> Self {
         let devices = Devices::default();
-        start(devices.clone());
+        start(devices.clone(), config.clone());
+
+        Self { devices, config }
+    }
 
-        Self { devices }
+    pub fn config(&self) -> &DsuConfig {
+        &self.config
     }
 }
 
@@ -46,7 +90,7 @@ impl Output for DsuOutput {
     }
 }
 
-fn start(devices: Devices) {
+fn start(devices: Devices, config: DsuConfig) {
     std::thread::spawn(move || {
         loop {
             devices.lock().unwrap().changed = true;
@@ -55,7 +99,7 @@ fn start(devices: Devices) {
 
             info!("starting dsu server thread");
 
-            match Thread::new(devices).and_then(Thread::run) {
+            match Thread::new(devices, config.clone()).and_then(Thread::run) {
                 Ok(()) => info!("dsu server thread exited"),
                 Err(e) => info!("dsu server thread error: {e:?}, restarting..."),
             }
@@ -67,6 +111,7 @@ fn start(devices: Devices) {
 
 struct Thread {
     devices: Devices,
+    config: DsuConfig,
     indexes: HashMap<DeviceId, usize>,
     reader: DeviceReader,
     server: Server,
@@ -74,13 +119,20 @@ struct Thread {
 
 impl Thread {
     const SERVER_ID: u32 = 0xDEDEDE00;
+    /// Packets handled per wakeup, so a flood of packets can't stop data from being sent.
+    const MAX_RECEIVE: usize = 64;
+    /// Longest wait before checking for new requests, since the socket doesn't wake the reader.
+    const RECEIVE_INTERVAL: Duration = Duration::from_millis(20);
+
+    fn new(devices: Devices, config: DsuConfig) -> Result<Self> {
+        let server = Server::new(true, Self::SERVER_ID, dsu::server::default_address())?.with_config(config.get());
 
-    fn new(devices: Devices) -> Result<Self> {
         Ok(Self {
             devices,
+            config,
             indexes: HashMap::new(),
             reader: DeviceReader::new(),
-            server: Server::new(true, Self::SERVER_ID, dsu::server::default_address())?,
+            server,
         })
     }
 
@@ -88,20 +140,31 @@ impl Thread {
         loop {
             self.update_reader()?;
 
-            if let Some(map) = self.reader.wait_timeout(Duration::from_millis(20)) {
-                for (id, input) in map {
-                    let Some(&index) = self.indexes.get(id)
-                    else { continue };
+            if let Some(config) = self.config.take_changed() {
+                self.server.set_config(config);
+            }
 
-                    let data = &mut self.server.controllers[index];
-                    Self::update(data, input);
-                }
+            // Input written while the last packets were being sent doesn't wake the reader,
+            // so the current values are applied even on timeout.
+            self.reader.wait_timeout(self.server.next_send().min(Self::RECEIVE_INTERVAL));
+            for (id, input) in self.reader.current() {
+                let Some(&index) = self.indexes.get(id)
+                else { continue };
+
+                let data = &mut self.server.controllers[index];
+                Self::update(data, input);
             }
             
             self.server.remove_old_clients();
-            match self.server.receive() {
-                Ok(()) => {}
-                Err(e) => warn!("dsu server receive error: {e:?}"),
+            for _ in 0..Self::MAX_RECEIVE {
+                match self.server.receive() {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(e) => {
+                        warn!("dsu server receive error: {e:?}");
+                        break;
+                    }
+                }
             }
             self.server.send();
         }
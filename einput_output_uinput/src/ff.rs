@@ -0,0 +1,93 @@
+use std::{collections::HashMap, time::{Duration, Instant}};
+
+use evdev::{FFEffectData, FFEffectKind};
+
+/// Force feedback effects uploaded to a virtual gamepad, played back on the source device as rumble.
+#[derive(Default)]
+pub struct Effects {
+    effects: HashMap<i16, Effect>,
+    gain: Option<u16>,
+}
+
+struct Effect {
+    data: FFEffectData,
+    playing: Option<Playback>,
+}
+
+struct Playback {
+    start: Instant,
+    /// How many more times the effect plays after the current one.
+    repeat: i32,
+}
+
+impl Effects {
+    pub const MAX: u32 = 16;
+
+    pub fn upload(&mut self, id: i16, data: FFEffectData) {
+        // Updating an effect that is playing keeps it playing with the new data.
+        let playing = self.effects.remove(&id).and_then(|effect| effect.playing);
+        self.effects.insert(id, Effect { data, playing });
+    }
+
+    pub fn erase(&mut self, id: i16) {
+        self.effects.remove(&id);
+    }
+
+    /// `count` is the number of times to play the effect, 0 stops it.
+    pub fn play(&mut self, id: i16, count: i32) {
+        let Some(effect) = self.effects.get_mut(&id)
+        else { return };
+
+        effect.playing = match count {
+            0 => None,
+            _ => Some(Playback { start: Instant::now(), repeat: count - 1 }),
+        };
+    }
+
+    pub fn set_gain(&mut self, gain: u16) {
+        self.gain = Some(gain);
+    }
+
+    /// Returns the strong and weak motor strengths at `now`.
+    pub fn rumble(&mut self, now: Instant) -> (u16, u16) {
+        let (mut strong, mut weak) = (0u32, 0u32);
+
+        for effect in self.effects.values_mut() {
+            let Some(playback) = &mut effect.playing
+            else { continue };
+
+            let delay = Duration::from_millis(effect.data.replay.delay as u64);
+            let length = Duration::from_millis(effect.data.replay.length as u64);
+
+            // A length of 0 plays the effect until it is stopped.
+            if length != Duration::ZERO {
+                while now >= playback.start + delay + length {
+                    if playback.repeat <= 0 {
+                        effect.playing = None;
+                        break;
+                    }
+
+                    playback.start += delay + length;
+                    playback.repeat -= 1;
+                }
+            }
+
+            let Some(playback) = &effect.playing
+            else { continue };
+
+            if now < playback.start + delay {
+                continue;
+            }
+
+            if let FFEffectKind::Rumble { strong_magnitude, weak_magnitude } = effect.data.kind {
+                strong += strong_magnitude as u32;
+                weak += weak_magnitude as u32;
+            }
+        }
+
+        let gain = self.gain.unwrap_or(u16::MAX) as u32;
+        let scale = |value: u32| (value.min(u16::MAX as u32) * gain / u16::MAX as u32) as u16;
+
+        (scale(strong), scale(weak))
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::HashMap, io::ErrorKind, os::fd::AsRawFd, time::{Duration, Instant}};
+
+use anyhow::{Context, Result};
+use einput_core::device::{Device, DeviceReader};
+use einput_device::{input::{buttons::Button, sticks::StickId, triggers::TriggerId}, DeviceInput, DeviceOutput};
+use einput_util::axis::StickAxis;
+use evdev::{uinput::VirtualDevice, AbsInfo, AbsoluteAxisCode, AttributeSet, BusType, EventSummary, EventType, FFEffectCode, InputEvent, InputId, KeyCode, UInputCode, UinputAbsSetup};
+use log::warn;
+
+use crate::{ff::Effects, Devices};
+
+pub fn run(devices: Devices) -> Result<()> {
+    let mut targets: Vec<Gamepad> = Vec::new();
+    let mut sources: Vec<Device> = Vec::new();
+    let mut index_map = HashMap::new();
+    let mut reader = DeviceReader::new();
+
+    loop {
+        {
+            let mut lock = devices.lock().unwrap();
+            if lock.changed {
+                lock.changed = false;
+
+                reader = DeviceReader::new();
+                index_map.clear();
+
+                for source in &sources {
+                    source.update_output(stop_rumble);
+                }
+                sources = lock.list.clone();
+
+                for (i, device) in lock.list.iter().enumerate() {
+                    index_map.insert(device.info().id().clone(), i);
+                    device.register_reader(&mut reader);
+                }
+
+                for target in &mut targets {
+                    target.rumble = None;
+                    target.events.clear();
+                }
+
+                if lock.list.len() < targets.len() {
+                    for _ in 0..(targets.len() - lock.list.len()) {
+                        targets.pop();
+                    }
+                } else if targets.len() < lock.list.len() {
+                    for _ in 0..(lock.list.len() - targets.len()) {
+                        let target = Gamepad::new(targets.len())
+                            .context("error creating uinput device")?;
+                        targets.push(target);
+                    }
+                }
+            }
+        }
+
+        // Input written while events were being emitted or force feedback was handled doesn't wake the reader,
+        // so the current values are applied even on timeout.
+        reader.wait_timeout(Duration::from_millis(20));
+        for (id, input) in reader.current() {
+            let Some(&index) = index_map.get(id)
+            else { continue };
+
+            let events = input_to_events(input);
+
+            let target = &mut targets[index];
+            if target.events == events {
+                continue;
+            }
+
+            match target.device.emit(&events) {
+                Ok(()) => target.events = events,
+                Err(e) => {
+                    warn!("error updating target: {e}");
+                }
+            }
+        }
+
+        let now = Instant::now();
+
+        for (target, source) in targets.iter_mut().zip(&sources) {
+            match target.poll_ff() {
+                Ok(()) => {}
+                Err(e) => warn!("error reading force feedback events: {e}"),
+            }
+
+            let rumble = target.effects.rumble(now);
+            if target.rumble == Some(rumble) {
+                continue;
+            }
+
+            target.rumble = Some(rumble);
+            source.update_output(|output| set_rumble(output, rumble));
+        }
+    }
+}
+
+fn set_rumble(output: &mut DeviceOutput, (strong, weak): (u16, u16)) {
+    match output.rumbles_mut() {
+        [] => {}
+        [motor] => motor.strength = (strong.max(weak) >> 8) as u8,
+        [first, second, ..] => {
+            first.strength = (strong >> 8) as u8;
+            second.strength = (weak >> 8) as u8;
+        }
+    }
+}
+
+fn stop_rumble(output: &mut DeviceOutput) {
+    for motor in output.rumbles_mut() {
+        motor.strength = 0;
+    }
+}
+
+/// A virtual gamepad with the same codes and ranges as an Xbox 360 controller under the `xpad` driver,
+/// so games and SDL recognize it without extra mappings.
+struct Gamepad {
+    device: VirtualDevice,
+    effects: Effects,
+    rumble: Option<(u16, u16)>,
+    /// Last events emitted, to skip inputs that haven't changed.
+    events: Vec<InputEvent>,
+}
+
+impl Gamepad {
+    // The kernel's BTN_X and BTN_Y are the same codes as BTN_NORTH and BTN_WEST.
+    const BUTTONS: [(Button, KeyCode); 11] = [
+        (Button::A, KeyCode::BTN_SOUTH),
+        (Button::B, KeyCode::BTN_EAST),
+        (Button::X, KeyCode::BTN_NORTH),
+        (Button::Y, KeyCode::BTN_WEST),
+        (Button::L1, KeyCode::BTN_TL),
+        (Button::R1, KeyCode::BTN_TR),
+        (Button::Select, KeyCode::BTN_SELECT),
+        (Button::Start, KeyCode::BTN_START),
+        (Button::Home, KeyCode::BTN_MODE),
+        (Button::LStick, KeyCode::BTN_THUMBL),
+        (Button::RStick, KeyCode::BTN_THUMBR),
+    ];
+
+    fn new(index: usize) -> Result<Self> {
+        let mut keys = AttributeSet::<KeyCode>::new();
+        for (_, key) in Self::BUTTONS {
+            keys.insert(key);
+        }
+
+        let stick = AbsInfo::new(0, i16::MIN as i32, i16::MAX as i32, 16, 128, 0);
+        let trigger = AbsInfo::new(0, 0, u8::MAX as i32, 0, 0, 0);
+        let hat = AbsInfo::new(0, -1, 1, 0, 0, 0);
+
+        let mut ff = AttributeSet::<FFEffectCode>::new();
+        ff.insert(FFEffectCode::FF_RUMBLE);
+        ff.insert(FFEffectCode::FF_GAIN);
+
+        let name = format!("einput Gamepad {}", index + 1);
+
+        let device = VirtualDevice::builder()?
+            .name(&name)
+            .input_id(InputId::new(BusType::BUS_USB, 0x045E, 0x028E, 0x0110))
+            .with_keys(&keys)?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_X, stick))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_Y, stick))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_RX, stick))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_RY, stick))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_Z, trigger))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_RZ, trigger))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_HAT0X, hat))?
+            .with_absolute_axis(&UinputAbsSetup::new(AbsoluteAxisCode::ABS_HAT0Y, hat))?
+            .with_ff(&ff)?
+            .with_ff_effects_max(Effects::MAX)
+            .build()?;
+
+        // Force feedback requests are polled between input updates.
+        let fd = device.as_raw_fd();
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        if flags < 0 || unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        Ok(Self {
+            device,
+            effects: Effects::default(),
+            rumble: None,
+            events: Vec::new(),
+        })
+    }
+
+    fn poll_ff(&mut self) -> Result<()> {
+        let events: Vec<InputEvent> = match self.device.fetch_events() {
+            Ok(events) => events.collect(),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        for event in events {
+            match event.destructure() {
+                EventSummary::UInput(event, UInputCode::UI_FF_UPLOAD, _) => {
+                    let upload = self.device.process_ff_upload(event)?;
+                    self.effects.upload(upload.effect_id(), upload.effect());
+                }
+                EventSummary::UInput(event, UInputCode::UI_FF_ERASE, _) => {
+                    let erase = self.device.process_ff_erase(event)?;
+                    self.effects.erase(erase.effect_id() as i16);
+                }
+                EventSummary::ForceFeedback(_, FFEffectCode::FF_GAIN, value) => {
+                    self.effects.set_gain(value as u16);
+                }
+                EventSummary::ForceFeedback(_, FFEffectCode(id), value) => {
+                    self.effects.play(id as i16, value);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The kernel drops events whose value didn't change, so the full state is sent every time.
+fn input_to_events(input: &DeviceInput) -> Vec<InputEvent> {
+    let mut events = Vec::new();
+
+    let buttons = input.buttons().copied().unwrap_or_default();
+
+    for (button, key) in Gamepad::BUTTONS {
+        events.push(InputEvent::new(EventType::KEY.0, key.0, buttons.is_pressed(button) as i32));
+    }
+
+    let hat_x = buttons.is_pressed(Button::Right) as i32 - buttons.is_pressed(Button::Left) as i32;
+    let hat_y = buttons.is_pressed(Button::Down) as i32 - buttons.is_pressed(Button::Up) as i32;
+
+    let l2 = if buttons.is_pressed(Button::L2) {
+        255
+    } else if let Some(l2) = input.get(TriggerId::L2) {
+        l2.0
+    } else {
+        0
+    };
+
+    let r2 = if buttons.is_pressed(Button::R2) {
+        255
+    } else if let Some(r2) = input.get(TriggerId::R2) {
+        r2.0
+    } else {
+        0
+    };
+
+    let left = input.get(StickId::Left).copied().unwrap_or_default();
+    let right = input.get(StickId::Right).copied().unwrap_or_default();
+
+    let axes: [(AbsoluteAxisCode, i16); 4] = [
+        (AbsoluteAxisCode::ABS_X, StickAxis::from_f32(left.x)),
+        (AbsoluteAxisCode::ABS_Y, StickAxis::from_f32(left.y)),
+        (AbsoluteAxisCode::ABS_RX, StickAxis::from_f32(right.x)),
+        (AbsoluteAxisCode::ABS_RY, StickAxis::from_f32(right.y)),
+    ];
+
+    for (axis, value) in axes {
+        events.push(InputEvent::new(EventType::ABSOLUTE.0, axis.0, value as i32));
+    }
+
+    events.push(InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Z.0, l2 as i32));
+    events.push(InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_RZ.0, r2 as i32));
+    events.push(InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_HAT0X.0, hat_x));
+    events.push(InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_HAT0Y.0, hat_y));
+
+    events
+}
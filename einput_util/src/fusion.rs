@@ -0,0 +1,124 @@
+use std::time::Instant;
+
+/// Gain of the accelerometer correction step, higher values remove gyroscope drift faster
+/// but let more accelerometer noise and movement through.
+const BETA: f32 = 0.1;
+
+/// Longest gap between samples that's integrated, so a stalled device doesn't jump.
+const MAX_DT: f32 = 0.1;
+
+/// Tracks a device's orientation from its gyroscope and accelerometer with a Madgwick filter.
+#[derive(Clone, Copy, Debug)]
+pub struct Fusion {
+    /// `[w, x, y, z]`, rotating the device's axes into the world's.
+    quaternion: [f32; 4],
+    last: Option<Instant>,
+}
+
+impl Default for Fusion {
+    fn default() -> Self {
+        Self {
+            quaternion: [1.0, 0.0, 0.0, 0.0],
+            last: None,
+        }
+    }
+}
+
+impl Fusion {
+    /// Orientation as `[w, x, y, z]`.
+    ///
+    /// Yaw isn't corrected by the accelerometer, so it drifts slowly.
+    pub fn quaternion(&self) -> [f32; 4] {
+        self.quaternion
+    }
+
+    /// Direction of gravity in the device's axes, with a length of 1.
+    pub fn gravity(&self) -> [f32; 3] {
+        let [w, x, y, z] = self.quaternion;
+
+        [
+            2.0 * (x * z - w * y),
+            2.0 * (w * x + y * z),
+            w * w - x * x - y * y + z * z,
+        ]
+    }
+
+    /// Adds a sample taken at `timestamp`, if it's newer than the last one.
+    ///
+    /// `gyro` is in degrees/second about the device's X, Y and Z axes, and `accel` is in any unit.
+    pub fn update(&mut self, timestamp: Instant, gyro: [f32; 3], accel: [f32; 3]) {
+        let Some(last) = self.last
+        else {
+            self.last = Some(timestamp);
+            self.reset(accel);
+            return;
+        };
+
+        if timestamp <= last {
+            return;
+        }
+
+        self.last = Some(timestamp);
+
+        let dt = (timestamp - last).as_secs_f32().min(MAX_DT);
+        self.integrate(gyro.map(f32::to_radians), accel, dt);
+    }
+
+    /// Points the orientation straight at the measured gravity.
+    fn reset(&mut self, accel: [f32; 3]) {
+        let Some([x, y, z]) = normalize(accel)
+        else { return };
+
+        // Shortest rotation from gravity to the world's up
+        self.quaternion = normalize([1.0 + z, y, -x, 0.0]).unwrap_or([0.0, 1.0, 0.0, 0.0]);
+    }
+
+    /// `gyro` in radians/second about the device's X, Y and Z axes.
+    fn integrate(&mut self, gyro: [f32; 3], accel: [f32; 3], dt: f32) {
+        let [q0, q1, q2, q3] = self.quaternion;
+        let [gx, gy, gz] = gyro;
+
+        // Rate of change from the gyroscope
+        let mut dq = [
+            0.5 * (-q1 * gx - q2 * gy - q3 * gz),
+            0.5 * (q0 * gx + q2 * gz - q3 * gy),
+            0.5 * (q0 * gy - q1 * gz + q3 * gx),
+            0.5 * (q0 * gz + q1 * gy - q2 * gx),
+        ];
+
+        // Gradient descent step towards the measured gravity
+        if let Some([ax, ay, az]) = normalize(accel) {
+            let [gravity_x, gravity_y, gravity_z] = self.gravity();
+            let [fx, fy, fz] = [gravity_x - ax, gravity_y - ay, gravity_z - az];
+
+            let step = [
+                -2.0 * q2 * fx + 2.0 * q1 * fy,
+                2.0 * q3 * fx + 2.0 * q0 * fy - 4.0 * q1 * fz,
+                -2.0 * q0 * fx + 2.0 * q3 * fy - 4.0 * q2 * fz,
+                2.0 * q1 * fx + 2.0 * q2 * fy,
+            ];
+
+            if let Some(step) = normalize(step) {
+                for (dq, step) in dq.iter_mut().zip(step) {
+                    *dq -= BETA * step;
+                }
+            }
+        }
+
+        let mut quaternion = self.quaternion;
+
+        for (q, dq) in quaternion.iter_mut().zip(dq) {
+            *q += dq * dt;
+        }
+
+        if let Some(quaternion) = normalize(quaternion) {
+            self.quaternion = quaternion;
+        }
+    }
+}
+
+fn normalize<const N: usize>(v: [f32; N]) -> Option<[f32; N]> {
+    let length = v.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+    (length > f32::EPSILON).then(|| v.map(|v| v / length))
+}
@@ -1,2 +1,3 @@
 pub mod axis;
+pub mod fusion;
 pub mod shared;